@@ -0,0 +1,334 @@
+#![deny(clippy::all, clippy::pedantic)]
+#![forbid(unsafe_code)]
+#![allow(clippy::module_name_repetitions)]
+
+//! Derive macros for the [problemdetails](https://crates.io/crates/problemdetails) crate.
+//!
+//! These are re-exported from `problemdetails` when the `derive` feature is enabled, and should be
+//! used from there instead of depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Fields, Ident, LitInt,
+    LitStr, Path,
+};
+
+/// Derive `From<T> for problemdetails::Problem`, so that `?` can turn the type into a `Problem`.
+///
+/// The `#[problem(...)]` attribute describes the problem to produce. On an enum it can be used on
+/// the enum itself, to give defaults for every variant, and on each variant. On a struct it is
+/// used on the struct itself. The supported values are:
+/// - `status = 404` - The status code of the problem. Defaults to 500.
+/// - `type = "..."` - The "type" of the problem.
+/// - `title = "..."` - The "title" of the problem.
+/// - `detail = "..."` - The "detail" of the problem. This is a format string that can refer to the
+///   fields, either by name or by position for tuple fields, in the same way as `format!`.
+/// - `skip_detail` - Do not fill in the "detail" from the `thiserror` message.
+///
+/// # thiserror
+/// Types that use [thiserror](https://crates.io/crates/thiserror) are supported directly. When a
+/// variant has an `#[error("...")]` attribute and no `detail`, the `Display` output of the error is
+/// used as the "detail". Use `skip_detail` on variants where this would leak internal details.
+/// This only affects the "detail", so any fields that hold the same details still need to be marked
+/// with `#[problem(skip)]`.
+///
+/// Fields marked with `#[source]`, `#[from]` or `#[backtrace]`, or named `source`, are not added as
+/// extension members. Instead, the error itself is kept as the source of the problem, so that the
+/// cause chain is available through `std::error::Error::source`. This requires the error type to
+/// be `Send + Sync + 'static`.
+///
+/// Every named field is added to the problem as an extension member, using `serde` to convert it
+/// into JSON. Fields can also be annotated with `#[problem(...)]`:
+/// - `skip` - Do not add the field to the problem.
+/// - `rename = "..."` - Use a different name for the extension member. Tuple fields are only added
+///   to the problem when they are given a name this way.
+///
+/// # Examples
+/// ```ignore
+/// #[derive(problemdetails::IntoProblem)]
+/// enum AccountError {
+///     #[problem(
+///         status = 403,
+///         type = "https://example.com/probs/out-of-credit",
+///         title = "You do not have enough credit.",
+///         detail = "Your current balance is {balance}, but that costs {cost}."
+///     )]
+///     OutOfCredit { balance: u32, cost: u32 },
+///     #[problem(status = 404, title = "Account not found")]
+///     NotFound(#[problem(rename = "account")] String),
+/// }
+/// ```
+#[proc_macro_derive(IntoProblem, attributes(problem))]
+pub fn derive_into_problem(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generate the `From` implementation for the type.
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let container = ProblemAttrs::parse(&input.attrs)?;
+    let mut is_error = container.has_error;
+
+    let arms = match &input.data {
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let ident = &variant.ident;
+                let attrs = ProblemAttrs::parse(&variant.attrs)?.or(&container);
+                is_error |= attrs.has_error;
+
+                expand_arm(&syn::parse_quote!(#name::#ident), &variant.fields, &attrs)
+            })
+            .collect::<syn::Result<Vec<_>>>()?,
+        Data::Struct(data) => vec![expand_arm(
+            &syn::parse_quote!(#name),
+            &data.fields,
+            &container,
+        )?],
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                input.span(),
+                "IntoProblem can not be derived for unions",
+            ))
+        },
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Types using `thiserror` are errors, so they are kept as the source of the problem.
+    let result = if is_error {
+        quote!(problem.with_source(__value))
+    } else {
+        quote!(problem)
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for ::problemdetails::Problem
+        #where_clause
+        {
+            fn from(__value: #name #ty_generics) -> Self {
+                #[allow(unused_variables)]
+                let problem = match &__value {
+                    #(#arms)*
+                };
+
+                #result
+            }
+        }
+    })
+}
+
+/// Generate the match arm that builds the problem for a single struct or enum variant.
+fn expand_arm(path: &Path, fields: &Fields, attrs: &ProblemAttrs) -> syn::Result<TokenStream2> {
+    let fields = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| FieldInfo::parse(index, field, attrs.has_error))
+        .collect::<syn::Result<Vec<_>>>()?;
+    let bindings = fields.iter().map(|field| &field.binding);
+
+    let pattern = match fields.first().map(|field| field.named) {
+        None => quote!(#path { .. }),
+        Some(true) => quote!(#path { #(#bindings),* }),
+        Some(false) => quote!(#path ( #(#bindings),* )),
+    };
+
+    let status = attrs.status.unwrap_or(500);
+    let mut builders = vec![];
+    if let Some(value) = &attrs.problem_type {
+        builders.push(quote!(.with_type(#value)));
+    }
+    if let Some(value) = &attrs.title {
+        builders.push(quote!(.with_title(#value)));
+    }
+    if let Some(value) = &attrs.detail {
+        let format = detail_format(value, fields.iter().any(|field| !field.named));
+        builders.push(quote!(.with_detail(::std::format!(#format))));
+    } else if attrs.has_error && !attrs.skip_detail {
+        builders.push(quote!(.with_detail(::std::string::ToString::to_string(&__value))));
+    }
+    for field in &fields {
+        if let Some(member) = &field.member {
+            let binding = &field.binding;
+            builders.push(quote! {
+                .with_value(
+                    #member,
+                    ::problemdetails::__private::serde_json::to_value(#binding).unwrap_or_default(),
+                )
+            });
+        }
+    }
+
+    Ok(quote! {
+        #pattern => ::problemdetails::new(
+            ::problemdetails::__private::StatusCode::from_u16(#status)
+                .expect("status code was checked by the derive macro"),
+        )
+        #(#builders)*,
+    })
+}
+
+/// Convert a `detail` format string so that it can refer to tuple fields by position.
+///
+/// Tuple fields are bound as `_0`, `_1`, and so on, so `{0}` is rewritten to `{_0}`.
+fn detail_format(detail: &LitStr, positional: bool) -> LitStr {
+    if !positional {
+        return detail.clone();
+    }
+
+    let value = detail.value();
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        result.push(c);
+        if c == '{' {
+            match chars.peek() {
+                Some('{') => result.extend(chars.next()),
+                Some(next) if next.is_ascii_digit() => result.push('_'),
+                _ => {},
+            }
+        }
+    }
+
+    LitStr::new(&result, detail.span())
+}
+
+/// The values from a `#[problem(...)]` attribute on a type or variant.
+#[derive(Default)]
+struct ProblemAttrs {
+    status:       Option<u16>,
+    problem_type: Option<LitStr>,
+    title:        Option<LitStr>,
+    detail:       Option<LitStr>,
+    /// Whether the "detail" should not be filled from the `#[error(...)]` message.
+    skip_detail:  bool,
+    /// Whether there is a `thiserror` `#[error(...)]` attribute.
+    has_error:    bool,
+}
+
+impl ProblemAttrs {
+    /// Parse the `#[problem(...)]` attributes from a type or variant.
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut result = Self {
+            has_error: attrs.iter().any(|attr| attr.path().is_ident("error")),
+            ..Self::default()
+        };
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("problem")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("status") {
+                    let lit: LitInt = meta.value()?.parse()?;
+                    let status = lit
+                        .base10_parse::<u16>()
+                        .ok()
+                        .filter(|status| (100..=999).contains(status))
+                        .ok_or_else(|| {
+                            syn::Error::new(lit.span(), "status must be between 100 and 999")
+                        })?;
+                    result.status = Some(status);
+                } else if meta.path.is_ident("type") {
+                    result.problem_type = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("title") {
+                    result.title = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("detail") {
+                    result.detail = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("skip_detail") {
+                    result.skip_detail = true;
+                } else {
+                    return Err(meta.error("unsupported problem attribute"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(result)
+    }
+
+    /// Fill in any values that are not set from the defaults.
+    fn or(self, defaults: &Self) -> Self {
+        Self {
+            status:       self.status.or(defaults.status),
+            problem_type: self.problem_type.or_else(|| defaults.problem_type.clone()),
+            title:        self.title.or_else(|| defaults.title.clone()),
+            detail:       self.detail.or_else(|| defaults.detail.clone()),
+            skip_detail:  self.skip_detail || defaults.skip_detail,
+            has_error:    self.has_error || defaults.has_error,
+        }
+    }
+}
+
+/// Details about a single field of a struct or variant.
+struct FieldInfo {
+    /// Whether the field is named, as opposed to a tuple field.
+    named:   bool,
+    /// The name that the field is bound to in the match arm.
+    binding: Ident,
+    /// The name of the extension member to add the field as, if any.
+    member:  Option<String>,
+}
+
+impl FieldInfo {
+    /// Parse a field, including any `#[problem(...)]` attributes on it.
+    ///
+    /// Fields that `thiserror` treats as the source or backtrace of the error are never added to
+    /// the problem.
+    fn parse(index: usize, field: &syn::Field, has_error: bool) -> syn::Result<Self> {
+        let mut skip = field.attrs.iter().any(|attr| {
+            ["source", "from", "backtrace"]
+                .iter()
+                .any(|name| attr.path().is_ident(name))
+        });
+        if has_error && field.ident.as_ref().is_some_and(|ident| ident == "source") {
+            skip = true;
+        }
+
+        let mut rename: Option<LitStr> = None;
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("problem"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                } else if meta.path.is_ident("rename") {
+                    rename = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unsupported problem attribute"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        let (named, binding) = match &field.ident {
+            Some(ident) => (true, ident.clone()),
+            None => (false, format_ident!("_{}", index)),
+        };
+
+        let member = if skip {
+            None
+        } else if let Some(rename) = rename {
+            Some(rename.value())
+        } else if named {
+            Some(binding.to_string().trim_start_matches("r#").to_owned())
+        } else {
+            None
+        };
+
+        Ok(Self {
+            named,
+            binding,
+            member,
+        })
+    }
+}
@@ -1,16 +1,11 @@
 use std::net::SocketAddr;
 
 use axum::{
-    extract::{
-        rejection::{self, BytesRejection, JsonRejection},
-        FromRequest,
-    },
-    response::IntoResponse,
     routing::{get, post},
     Router,
 };
 use http::StatusCode;
-use problemdetails::{axum::PanicHandlerBuilder, Problem};
+use problemdetails::axum::{ConvertErrorsLayer, Json, PanicHandlerBuilder};
 use serde::Deserialize;
 
 #[tokio::main]
@@ -29,6 +24,9 @@ async fn main() {
             .build(),
     );
 
+    // turn any other error responses, such as unmatched routes, into problems
+    let app = app.layer(ConvertErrorsLayer::new());
+
     // run it
     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
     println!("listening on {}", addr);
@@ -54,7 +52,7 @@ async fn panic() {
     panic!("Oh no!");
 }
 
-// example how to handle JSON rejection and return a problemdetail
+// example of a JSON body that is rejected with a problem if it is invalid
 
 // some example JSON deserializable data
 #[derive(Debug, Deserialize)]
@@ -62,46 +60,8 @@ struct ExampleData {
     name: String,
 }
 
-// an example handler with our special MyJson extractor
-async fn json(MyJson(data): MyJson<ExampleData>) {
+// an example handler with the problemdetails Json extractor
+async fn json(Json(data): Json<ExampleData>) {
     println!("{:?}", data);
     println!("{}", data.name);
 }
-
-pub struct MyRejection(Problem);
-
-#[derive(FromRequest)]
-#[from_request(via(axum::Json), rejection(MyRejection))]
-pub struct MyJson<T>(pub T);
-
-impl IntoResponse for MyRejection {
-    fn into_response(self) -> axum::response::Response {
-        return self.0.into_response();
-    }
-}
-
-impl From<JsonRejection> for MyRejection {
-    fn from(rejection: rejection::JsonRejection) -> Self {
-        let (status, body, title) = match rejection {
-            JsonRejection::JsonDataError(err) => {
-                (err.status(), err.body_text(), "JSON deserialization error")
-            },
-            JsonRejection::JsonSyntaxError(err) => {
-                (err.status(), err.body_text(), "JSON syntax error")
-            },
-            JsonRejection::MissingJsonContentType(err) => {
-                (err.status(), err.body_text(), "Content type error")
-            },
-            JsonRejection::BytesRejection(BytesRejection::FailedToBufferBody(err)) => {
-                (err.status(), err.body_text(), "Request buffering error")
-            },
-            _ => (StatusCode::BAD_GATEWAY, "".to_string(), "Unknown error"),
-        };
-
-        MyRejection(
-            problemdetails::new(status)
-                .with_title(body)
-                .with_detail(title),
-        )
-    }
-}
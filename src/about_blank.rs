@@ -0,0 +1,172 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::Problem;
+
+/// The "type" that RFC-9457 says a problem has when it doesn't have one.
+pub(crate) const ABOUT_BLANK: &str = "about:blank";
+
+/// Whether `about:blank` problems get a title by default, as set by [`set_about_blank`].
+static FILL_TITLE: AtomicBool = AtomicBool::new(false);
+
+/// Policy for the `title` member of `about:blank` problems in responses.
+///
+/// RFC-9457 says that a problem without a "type" has the type `about:blank`, and that the "title"
+/// of such a problem should be the same as the reason phrase of the status code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AboutBlank {
+    /// The body is sent exactly as it was built.
+    #[default]
+    Manual,
+    /// The `title` member is filled in from the reason phrase of the status code, if it hasn't
+    /// been set manually. This means that problems with an empty body are sent with a body.
+    FillTitle,
+}
+
+/// Set the policy for `about:blank` problems that is used by every problem that doesn't specify
+/// its own.
+///
+/// By default this is [`AboutBlank::Manual`].
+///
+/// # Examples
+/// ```
+/// # use http::StatusCode;
+/// use problemdetails::AboutBlank;
+///
+/// problemdetails::set_about_blank(AboutBlank::FillTitle);
+///
+/// let problem = problemdetails::new(StatusCode::NOT_FOUND);
+/// assert_eq!(problem.response_body()["title"], "Not Found");
+/// ```
+pub fn set_about_blank(policy: AboutBlank) {
+    FILL_TITLE.store(policy == AboutBlank::FillTitle, Ordering::Relaxed);
+}
+
+impl Problem {
+    /// Specify the policy for `about:blank` problems to use for this problem, instead of the one
+    /// set by [`set_about_blank`].
+    #[must_use]
+    pub fn with_about_blank(mut self, policy: AboutBlank) -> Self {
+        self.about_blank = Some(policy);
+
+        self
+    }
+
+    /// Get the policy for `about:blank` problems that applies to this problem.
+    #[must_use]
+    pub fn about_blank(&self) -> AboutBlank {
+        self.about_blank.unwrap_or_else(|| {
+            if FILL_TITLE.load(Ordering::Relaxed) {
+                AboutBlank::FillTitle
+            } else {
+                AboutBlank::Manual
+            }
+        })
+    }
+
+    /// Check if this problem has the type `about:blank`, either because it has no "type" or
+    /// because it was set to `about:blank`.
+    #[must_use]
+    pub fn is_about_blank(&self) -> bool {
+        match self.body.get("type") {
+            None => true,
+            Some(problem_type) => problem_type == ABOUT_BLANK,
+        }
+    }
+
+    /// Get the title to add to the body of the response, according to the [`AboutBlank`] policy
+    /// for this problem.
+    pub(crate) fn about_blank_title(&self) -> Option<&'static str> {
+        if self.about_blank() == AboutBlank::Manual
+            || !self.is_about_blank()
+            || self.body.contains_key("title")
+        {
+            return None;
+        }
+
+        self.status_code.canonical_reason()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use http::StatusCode;
+    use insta::assert_json_snapshot;
+
+    use super::AboutBlank;
+
+    #[test]
+    fn manual() {
+        let problem = crate::new(StatusCode::NOT_FOUND).with_about_blank(AboutBlank::Manual);
+
+        check!(problem.response_body().is_empty());
+    }
+
+    #[test]
+    fn fill_title() {
+        let problem = crate::new(StatusCode::NOT_FOUND).with_about_blank(AboutBlank::FillTitle);
+
+        assert_json_snapshot!(problem.response_body(), @r###"
+        {
+          "title": "Not Found"
+        }
+        "###);
+    }
+
+    #[test]
+    fn fill_title_with_explicit_about_blank() {
+        let problem = crate::new(StatusCode::CONFLICT)
+            .with_type("about:blank")
+            .with_detail("Somebody else changed it first.")
+            .with_about_blank(AboutBlank::FillTitle);
+
+        assert_json_snapshot!(problem.response_body(), @r###"
+        {
+          "detail": "Somebody else changed it first.",
+          "title": "Conflict",
+          "type": "about:blank"
+        }
+        "###);
+    }
+
+    #[test]
+    fn keep_manual_title() {
+        let problem = crate::new(StatusCode::NOT_FOUND)
+            .with_title("No such user")
+            .with_about_blank(AboutBlank::FillTitle);
+
+        check!(problem.response_body()["title"] == "No such user");
+    }
+
+    #[test]
+    fn other_types_are_unchanged() {
+        let problem = crate::new(StatusCode::FORBIDDEN)
+            .with_type("https://example.com/probs/out-of-credit")
+            .with_about_blank(AboutBlank::FillTitle);
+
+        check!(!problem.is_about_blank());
+        check!(problem.response_body().get("title") == None);
+    }
+
+    #[test]
+    fn unknown_status_code() {
+        let problem =
+            crate::new(StatusCode::from_u16(599).unwrap()).with_about_blank(AboutBlank::FillTitle);
+
+        check!(problem.response_body().is_empty());
+    }
+
+    #[test]
+    fn with_status_member() {
+        let problem = crate::new(StatusCode::NOT_FOUND)
+            .with_about_blank(AboutBlank::FillTitle)
+            .with_status_member(crate::StatusMember::Insert);
+
+        assert_json_snapshot!(problem.response_body(), @r###"
+        {
+          "status": 404,
+          "title": "Not Found"
+        }
+        "###);
+    }
+}
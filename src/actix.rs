@@ -0,0 +1,531 @@
+use std::{
+    any::Any,
+    future::{ready, Future, Ready},
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use actix_web::{
+    body::BoxBody,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    error::{JsonPayloadError, PathError, QueryPayloadError},
+    http::{header::CONTENT_TYPE, StatusCode},
+    HttpRequest, HttpResponse, Responder, ResponseError,
+};
+
+pub use super::types::{
+    BODY_TOO_LARGE, FAILED_TO_READ_BODY, INVALID_PATH_PARAMS, INVALID_QUERY_STRING,
+    JSON_DATA_ERROR, JSON_SYNTAX_ERROR, MISSING_JSON_CONTENT_TYPE,
+};
+use super::{Format, Problem, ProblemType};
+
+/// Actix uses an older version of the `http` crate, so status codes have to be converted.
+fn status_code(status_code: http::StatusCode) -> StatusCode {
+    StatusCode::from_u16(status_code.as_u16()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Build the response for a problem.
+///
+/// The `Problem` is kept in the extensions of the response, so that middleware can inspect it.
+fn problem_response(problem: Problem) -> HttpResponse {
+    let (status, body) = problem.response_parts();
+    let mut response = match body {
+        Some(body) => HttpResponse::build(status_code(status))
+            .insert_header((CONTENT_TYPE, Format::ProblemJson.content_type()))
+            .body(body),
+        None => HttpResponse::new(status_code(status)),
+    };
+
+    response.extensions_mut().insert(problem);
+    response
+}
+
+/// Allows a `Problem` to be returned as an `actix_web::Error`, for example with `?` from a handler
+/// that returns `actix_web::Result`.
+impl ResponseError for Problem {
+    fn status_code(&self) -> StatusCode {
+        status_code(self.status_code)
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        problem_response(self.clone())
+    }
+}
+
+impl Responder for Problem {
+    type Body = BoxBody;
+
+    fn respond_to(self, _request: &HttpRequest) -> HttpResponse {
+        problem_response(self)
+    }
+}
+
+/// Build the problem for an extractor error, keeping the status code and message from Actix.
+///
+/// The error enums are non-exhaustive, so errors that Actix adds in the future are converted
+/// without a "type". The message is only used as the "detail" of server errors if enabled with
+/// [`set_fill_detail`](crate::set_fill_detail).
+fn extractor_problem<E>(problem_type: Option<&ProblemType>, err: &E) -> Problem
+where
+    E: ResponseError,
+{
+    let status = http::StatusCode::from_u16(err.status_code().as_u16())
+        .unwrap_or(http::StatusCode::INTERNAL_SERVER_ERROR);
+
+    crate::rejection::rejection_problem(
+        problem_type,
+        status,
+        err.to_string(),
+        crate::detail::fill_detail(),
+    )
+}
+
+impl From<JsonPayloadError> for Problem {
+    fn from(err: JsonPayloadError) -> Self {
+        let problem_type = match &err {
+            JsonPayloadError::OverflowKnownLength { .. } | JsonPayloadError::Overflow { .. } => {
+                Some(&BODY_TOO_LARGE)
+            },
+            JsonPayloadError::ContentType => Some(&MISSING_JSON_CONTENT_TYPE),
+            JsonPayloadError::Deserialize(source) if source.is_data() => Some(&JSON_DATA_ERROR),
+            JsonPayloadError::Deserialize(_) => Some(&JSON_SYNTAX_ERROR),
+            JsonPayloadError::Payload(_) => Some(&FAILED_TO_READ_BODY),
+            _ => None,
+        };
+
+        extractor_problem(problem_type, &err)
+    }
+}
+
+impl From<PathError> for Problem {
+    fn from(err: PathError) -> Self {
+        extractor_problem(Some(&INVALID_PATH_PARAMS), &err)
+    }
+}
+
+impl From<QueryPayloadError> for Problem {
+    fn from(err: QueryPayloadError) -> Self {
+        extractor_problem(Some(&INVALID_QUERY_STRING), &err)
+    }
+}
+
+/// Error handler for `actix_web::web::JsonConfig`, that rejects the request with a `Problem`.
+///
+/// # Examples
+/// ```
+/// use actix_web::{web, App};
+///
+/// let app = App::new().app_data(
+///     web::JsonConfig::default().error_handler(problemdetails::actix::json_error_handler),
+/// );
+/// ```
+#[must_use]
+pub fn json_error_handler(err: JsonPayloadError, _request: &HttpRequest) -> actix_web::Error {
+    Problem::from(err).into()
+}
+
+/// Error handler for `actix_web::web::PathConfig`, that rejects the request with a `Problem`.
+#[must_use]
+pub fn path_error_handler(err: PathError, _request: &HttpRequest) -> actix_web::Error {
+    Problem::from(err).into()
+}
+
+/// Error handler for `actix_web::web::QueryConfig`, that rejects the request with a `Problem`.
+#[must_use]
+pub fn query_error_handler(err: QueryPayloadError, _request: &HttpRequest) -> actix_web::Error {
+    Problem::from(err).into()
+}
+
+/// Builder for a [`CatchPanic`] middleware that turns panics into problem responses.
+///
+/// # Examples
+/// ```
+/// # use http::StatusCode;
+/// use actix_web::App;
+/// use problemdetails::actix::PanicHandlerBuilder;
+///
+/// let app = App::new().wrap(
+///     PanicHandlerBuilder::new()
+///         .with_problem(problemdetails::new(StatusCode::SERVICE_UNAVAILABLE))
+///         .build(),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct PanicHandlerBuilder {
+    fill_detail: bool,
+    problem:     Problem,
+}
+
+impl Default for PanicHandlerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PanicHandlerBuilder {
+    /// Create a builder for a panic handler that sends a 500 Internal Server Error.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            fill_detail: crate::detail::fill_detail(),
+            problem:     crate::new(http::StatusCode::INTERNAL_SERVER_ERROR)
+                .with_title(crate::INTERNAL_SERVER_ERROR_TITLE),
+        }
+    }
+
+    /// Enable automatic setting of the `Problem` `detail` field to the panic message.
+    /// By default it is the value of [`crate::set_fill_detail`] when the builder is created.
+    #[must_use]
+    pub fn with_fill_detail(mut self, enabled: bool) -> Self {
+        self.fill_detail = enabled;
+
+        self
+    }
+
+    /// Set the base problem to be used by the panic handler.
+    /// If `fill_detail` is enabled, the `detail` field will be replaced with the panic message.
+    #[must_use]
+    pub fn with_problem(mut self, problem: Problem) -> Self {
+        self.problem = problem;
+
+        self
+    }
+
+    /// Build the panic handler.
+    #[must_use]
+    pub fn build(self) -> CatchPanic {
+        CatchPanic { handler: self }
+    }
+}
+
+/// Middleware that turns panics in the services it wraps into problem responses. Created by
+/// [`PanicHandlerBuilder`].
+#[derive(Debug, Clone)]
+pub struct CatchPanic {
+    handler: PanicHandlerBuilder,
+}
+
+impl<S, B> Transform<S, ServiceRequest> for CatchPanic
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+    type InitError = ();
+    type Response = ServiceResponse<B>;
+    type Transform = CatchPanicService<S>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(CatchPanicService {
+            service,
+            handler: self.handler.clone(),
+        }))
+    }
+}
+
+/// Service that turns panics into problem responses. Created by [`CatchPanic`].
+///
+/// The problem is returned as an error, which Actix sends using the `ResponseError` implementation
+/// for `Problem`.
+#[derive(Debug)]
+pub struct CatchPanicService<S> {
+    service: S,
+    handler: PanicHandlerBuilder,
+}
+
+impl<S, B> Service<ServiceRequest> for CatchPanicService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+    type Response = ServiceResponse<B>;
+
+    fn poll_ready(&self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let handler = self.handler.clone();
+        let future = catch_unwind(AssertUnwindSafe(|| self.service.call(request)));
+
+        Box::pin(async move {
+            let result = match future {
+                Ok(future) => CatchUnwind(Box::pin(future)).await,
+                Err(err) => Err(err),
+            };
+
+            result.unwrap_or_else(|err| {
+                Err(
+                    crate::panic::panic_problem(&handler.problem, handler.fill_detail, &*err)
+                        .into(),
+                )
+            })
+        })
+    }
+}
+
+/// Future that catches a panic while polling the inner future.
+struct CatchUnwind<F>(Pin<Box<F>>);
+
+impl<F> Future for CatchUnwind<F>
+where
+    F: Future,
+{
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let inner = &mut self.get_mut().0;
+
+        match catch_unwind(AssertUnwindSafe(|| inner.as_mut().poll(cx))) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(err) => Poll::Ready(Err(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        body::to_bytes,
+        http::{header::CONTENT_TYPE, StatusCode},
+        test::{
+            call_service, init_service, read_body, read_body_json, try_call_service, TestRequest,
+        },
+        web, App, HttpResponse,
+    };
+    use assert2::check;
+    use insta::assert_json_snapshot;
+    use serde::Deserialize;
+    use serde_json::Value;
+
+    use super::PanicHandlerBuilder;
+    use crate::Problem;
+
+    #[derive(Debug, Deserialize)]
+    struct User {
+        name: String,
+    }
+
+    async fn empty_problem() -> Problem {
+        crate::new(http::StatusCode::BAD_REQUEST)
+    }
+
+    async fn forbidden() -> actix_web::Result<HttpResponse> {
+        Err(crate::new(http::StatusCode::FORBIDDEN)
+            .with_type("https://example.com/probs/out-of-credit")
+            .with_title("You do not have enough credit.")
+            .with_detail("Your current balance is 30, but that costs 50.")
+            .with_value("balance", 30)
+            .into())
+    }
+
+    async fn create_user(user: web::Json<User>) -> String {
+        user.into_inner().name
+    }
+
+    async fn get_user(id: web::Path<u32>) -> String {
+        id.to_string()
+    }
+
+    async fn search(query: web::Query<User>) -> String {
+        query.into_inner().name
+    }
+
+    async fn panic() -> HttpResponse {
+        panic!("Panic message");
+    }
+
+    macro_rules! app {
+        ($panic_handler:expr) => {
+            init_service(
+                App::new()
+                    .wrap($panic_handler.build())
+                    .app_data(web::JsonConfig::default().error_handler(super::json_error_handler))
+                    .app_data(web::PathConfig::default().error_handler(super::path_error_handler))
+                    .app_data(web::QueryConfig::default().error_handler(super::query_error_handler))
+                    .route("/empty", web::get().to(empty_problem))
+                    .route("/forbidden", web::get().to(forbidden))
+                    .route("/users", web::post().to(create_user))
+                    .route("/users/{id}", web::get().to(get_user))
+                    .route("/search", web::get().to(search))
+                    .route("/panic", web::get().to(panic)),
+            )
+            .await
+        };
+    }
+
+    #[actix_web::test]
+    async fn no_values() {
+        let app = app!(PanicHandlerBuilder::new());
+
+        let response = call_service(&app, TestRequest::get().uri("/empty").to_request()).await;
+
+        check!(response.status() == StatusCode::BAD_REQUEST);
+        check!(response.headers().get(CONTENT_TYPE) == None);
+        check!(read_body(response).await.is_empty());
+    }
+
+    #[actix_web::test]
+    async fn error() {
+        let app = app!(PanicHandlerBuilder::new());
+
+        let response = call_service(&app, TestRequest::get().uri("/forbidden").to_request()).await;
+
+        check!(response.status() == StatusCode::FORBIDDEN);
+        check!(response.headers().get(CONTENT_TYPE).unwrap() == "application/problem+json");
+        check!(response.response().extensions().get::<Problem>().is_some());
+        assert_json_snapshot!(read_body_json::<Value, _>(response).await, @r###"
+        {
+          "balance": 30,
+          "detail": "Your current balance is 30, but that costs 50.",
+          "title": "You do not have enough credit.",
+          "type": "https://example.com/probs/out-of-credit"
+        }
+        "###);
+    }
+
+    #[actix_web::test]
+    async fn json_error() {
+        let app = app!(PanicHandlerBuilder::new());
+
+        let request = TestRequest::post()
+            .uri("/users")
+            .insert_header((CONTENT_TYPE, "application/json"))
+            .set_payload("{")
+            .to_request();
+        let response = call_service(&app, request).await;
+
+        check!(response.status() == StatusCode::BAD_REQUEST);
+        check!(response.headers().get(CONTENT_TYPE).unwrap() == "application/problem+json");
+        assert_json_snapshot!(read_body_json::<Value, _>(response).await, @r###"
+        {
+          "detail": "Json deserialize error: EOF while parsing an object at line 1 column 1",
+          "title": "Invalid JSON syntax",
+          "type": "tag:sazzer.github.io,2026:problemdetails/json-syntax-error"
+        }
+        "###);
+    }
+
+    #[actix_web::test]
+    async fn json_content_type_error() {
+        let app = app!(PanicHandlerBuilder::new());
+
+        let request = TestRequest::post()
+            .uri("/users")
+            .insert_header((CONTENT_TYPE, "text/plain"))
+            .set_payload("{}")
+            .to_request();
+        let response = call_service(&app, request).await;
+
+        check!(response.status() == StatusCode::BAD_REQUEST);
+        let body: Value = read_body_json(response).await;
+        check!(body["type"] == super::MISSING_JSON_CONTENT_TYPE.uri());
+        check!(body["detail"] == "Content type error");
+    }
+
+    #[actix_web::test]
+    async fn path_error() {
+        let app = app!(PanicHandlerBuilder::new());
+
+        let response = call_service(&app, TestRequest::get().uri("/users/abc").to_request()).await;
+
+        check!(response.status() == StatusCode::BAD_REQUEST);
+        let body: Value = read_body_json(response).await;
+        check!(body["type"] == super::INVALID_PATH_PARAMS.uri());
+        check!(body["title"] == "Invalid path parameters");
+    }
+
+    #[actix_web::test]
+    async fn query_error() {
+        let app = app!(PanicHandlerBuilder::new());
+
+        let response = call_service(&app, TestRequest::get().uri("/search").to_request()).await;
+
+        check!(response.status() == StatusCode::BAD_REQUEST);
+        assert_json_snapshot!(read_body_json::<Value, _>(response).await, @r###"
+        {
+          "detail": "Query deserialize error: missing field `name`",
+          "title": "Invalid query string",
+          "type": "tag:sazzer.github.io,2026:problemdetails/invalid-query-string"
+        }
+        "###);
+    }
+
+    #[actix_web::test]
+    async fn success() {
+        let app = app!(PanicHandlerBuilder::new());
+
+        let response = call_service(
+            &app,
+            TestRequest::get().uri("/search?name=Bob").to_request(),
+        )
+        .await;
+
+        check!(response.status() == StatusCode::OK);
+        check!(read_body(response).await == "Bob");
+    }
+
+    async fn panic_response(panic_handler: PanicHandlerBuilder) -> (StatusCode, Value) {
+        let app = app!(panic_handler);
+
+        let err = try_call_service(&app, TestRequest::get().uri("/panic").to_request())
+            .await
+            .unwrap_err();
+        let response = err.error_response();
+
+        check!(response.headers().get(CONTENT_TYPE).unwrap() == "application/problem+json");
+        let body = to_bytes(response.into_body()).await.unwrap();
+
+        (
+            err.as_response_error().status_code(),
+            serde_json::from_slice(&body).unwrap(),
+        )
+    }
+
+    #[actix_web::test]
+    async fn default_panic() {
+        let (status, body) = panic_response(PanicHandlerBuilder::new()).await;
+
+        check!(status == StatusCode::INTERNAL_SERVER_ERROR);
+        assert_json_snapshot!(body, @r###"
+        {
+          "detail": "Panic message",
+          "title": "Internal server error"
+        }
+        "###);
+    }
+
+    #[actix_web::test]
+    async fn fill_panic_message_false() {
+        let (status, body) =
+            panic_response(PanicHandlerBuilder::new().with_fill_detail(false)).await;
+
+        check!(status == StatusCode::INTERNAL_SERVER_ERROR);
+        assert_json_snapshot!(body, @r###"
+        {
+          "title": "Internal server error"
+        }
+        "###);
+    }
+
+    #[actix_web::test]
+    async fn customized_panic() {
+        let (status, body) = panic_response(PanicHandlerBuilder::new().with_problem(
+            crate::new(http::StatusCode::IM_A_TEAPOT).with_instance("some instance"),
+        ))
+        .await;
+
+        check!(status == StatusCode::IM_A_TEAPOT);
+        assert_json_snapshot!(body, @r###"
+        {
+          "detail": "Panic message",
+          "instance": "some instance"
+        }
+        "###);
+    }
+}
@@ -1,52 +1,415 @@
-use std::any::Any;
+use std::{
+    any::Any,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 use axum::{
     body::Body,
-    http::header::CONTENT_TYPE,
-    http::StatusCode,
-    response::{IntoResponse, Response},
-    Json,
+    http::{
+        header::{ACCEPT, CONTENT_LENGTH, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::{Html, IntoResponse, Response},
+    routing::get,
+    Router,
 };
-use http::HeaderValue;
+use http::{HeaderValue, Request};
 use tower_http::catch_panic::{CatchPanicLayer, ResponseForPanic};
+use tower_layer::Layer;
+use tower_service::Service;
+
+pub use self::extract::{Json, Path, Query};
+use super::{EmptyProblem, Format, Negotiator, Problem, ProblemTypeRegistry};
 
-use super::Problem;
+mod extract;
+pub mod rejection;
 
+/// The `Problem` is kept in the extensions of the response, so that layers such as
+/// [`NegotiateLayer`] can render it again.
 impl IntoResponse for Problem {
     fn into_response(self) -> Response {
-        if self.body.is_empty() {
-            self.status_code.into_response()
-        } else {
-            let body = Json(self.body);
-            let mut response = (self.status_code, body).into_response();
-
-            response.headers_mut().insert(
-                CONTENT_TYPE,
-                HeaderValue::from_static("application/problem+json"),
-            );
-            response
+        self.into_http_response().map(Body::from)
+    }
+}
+
+/// Layer that renders problem responses in the format selected by the `Accept` header of the
+/// request, using a [`Negotiator`].
+///
+/// This applies to every response that was produced from a `Problem`. Problems with an empty body
+/// are left as they are.
+///
+/// # Examples
+/// ```
+/// use problemdetails::{axum::NegotiateLayer, Format, Negotiator};
+///
+/// let router: axum::Router = axum::Router::new().layer(NegotiateLayer::new(
+///     Negotiator::new()
+///         .with_fallback(Format::Text)
+///         .with_json_compatibility(true),
+/// ));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NegotiateLayer {
+    negotiator: Negotiator,
+}
+
+impl NegotiateLayer {
+    /// Create a layer that uses the given negotiator.
+    #[must_use]
+    pub fn new(negotiator: Negotiator) -> Self {
+        Self { negotiator }
+    }
+}
+
+impl<S> Layer<S> for NegotiateLayer {
+    type Service = Negotiate<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Negotiate {
+            inner,
+            negotiator: self.negotiator,
+        }
+    }
+}
+
+/// Service that renders problem responses in the format selected by the `Accept` header of the
+/// request. Created by [`NegotiateLayer`].
+#[derive(Debug, Clone, Copy)]
+pub struct Negotiate<S> {
+    inner:      S,
+    negotiator: Negotiator,
+}
+
+impl<S, B> Service<Request<B>> for Negotiate<S>
+where
+    S: Service<Request<B>, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+    type Response = Response;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let format = self.negotiator.negotiate(request.headers().get(ACCEPT));
+        let future = self.inner.call(request);
+
+        Box::pin(async move { Ok(render_response(future.await?, format)) })
+    }
+}
+
+/// Render the `Problem` that a response was produced from in the given format, if there is one.
+fn render_response(response: Response, format: Format) -> Response {
+    let Some(problem) = response.extensions().get::<Problem>() else {
+        return response;
+    };
+    if problem.response_body().is_empty() {
+        return response;
+    }
+
+    let (format, body) = problem.render(format);
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(CONTENT_LENGTH);
+    parts.headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static(format.content_type()),
+    );
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Layer that applies an [`EmptyProblem`] policy to every response that was produced from a
+/// `Problem` with an empty body.
+///
+/// If this is used along with [`NegotiateLayer`], it should be added before it so that
+/// `about:blank` problems are then rendered in the negotiated format.
+///
+/// # Examples
+/// ```
+/// use problemdetails::{
+///     axum::{EmptyProblemLayer, NegotiateLayer},
+///     EmptyProblem,
+/// };
+///
+/// let router: axum::Router = axum::Router::new()
+///     .layer(EmptyProblemLayer::new(EmptyProblem::AboutBlank))
+///     .layer(NegotiateLayer::default());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmptyProblemLayer {
+    policy: EmptyProblem,
+}
+
+impl EmptyProblemLayer {
+    /// Create a layer that uses the given policy.
+    #[must_use]
+    pub fn new(policy: EmptyProblem) -> Self {
+        Self { policy }
+    }
+}
+
+impl<S> Layer<S> for EmptyProblemLayer {
+    type Service = FillEmptyProblem<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        FillEmptyProblem {
+            inner,
+            policy: self.policy,
+        }
+    }
+}
+
+/// Service that applies an [`EmptyProblem`] policy to problem responses. Created by
+/// [`EmptyProblemLayer`].
+#[derive(Debug, Clone, Copy)]
+pub struct FillEmptyProblem<S> {
+    inner:  S,
+    policy: EmptyProblem,
+}
+
+impl<S, B> Service<Request<B>> for FillEmptyProblem<S>
+where
+    S: Service<Request<B>, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+    type Response = Response;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let policy = self.policy;
+        let future = self.inner.call(request);
+
+        Box::pin(async move { Ok(fill_empty_problem(future.await?, policy)) })
+    }
+}
+
+/// Apply the policy to the response if it was produced from a `Problem` with an empty body.
+fn fill_empty_problem(response: Response, policy: EmptyProblem) -> Response {
+    let Some(problem) = response.extensions().get::<Problem>() else {
+        return response;
+    };
+    if !problem.response_body().is_empty() {
+        return response;
+    }
+    let Some((problem, body)) = policy.fill(problem) else {
+        return response;
+    };
+
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(CONTENT_LENGTH);
+    parts.headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static(Format::ProblemJson.content_type()),
+    );
+    parts.extensions.insert(problem);
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// The largest response body that [`ConvertErrorsLayer`] will read to use as the `detail`.
+const DETAIL_LIMIT: usize = 16 * 1024;
+
+/// Layer that converts error responses that are not already problems into a `Problem`.
+///
+/// This applies to every 4xx and 5xx response that wasn't produced from a `Problem` and isn't
+/// already `application/problem+json` or `application/problem+xml`. This includes Axum's built-in
+/// rejections, and the 404 and 405 responses for requests that don't match a route. The status
+/// code and headers of the response are kept, and the `title` is the reason phrase of the status
+/// code.
+///
+/// # Examples
+/// ```
+/// use problemdetails::axum::ConvertErrorsLayer;
+///
+/// let router: axum::Router =
+///     axum::Router::new().layer(ConvertErrorsLayer::new().with_fill_detail(true));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ConvertErrorsLayer {
+    fill_detail: bool,
+}
+
+impl Default for ConvertErrorsLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConvertErrorsLayer {
+    /// Create a layer that converts error responses into problems.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            fill_detail: crate::detail::fill_detail(),
+        }
+    }
+
+    /// Enable setting the `detail` of the problem to the body of the original response, if it is
+    /// text.
+    ///
+    /// By default it is the value of [`crate::set_fill_detail`] when the builder is created.
+    #[must_use]
+    pub fn with_fill_detail(mut self, enabled: bool) -> Self {
+        self.fill_detail = enabled;
+
+        self
+    }
+}
+
+impl<S> Layer<S> for ConvertErrorsLayer {
+    type Service = ConvertErrors<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConvertErrors {
+            inner,
+            fill_detail: self.fill_detail,
+        }
+    }
+}
+
+/// Service that converts error responses that are not already problems into a `Problem`. Created
+/// by [`ConvertErrorsLayer`].
+#[derive(Debug, Clone, Copy)]
+pub struct ConvertErrors<S> {
+    inner:       S,
+    fill_detail: bool,
+}
+
+impl<S, B> Service<Request<B>> for ConvertErrors<S>
+where
+    S: Service<Request<B>, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+    type Response = Response;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let fill_detail = self.fill_detail;
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let response = future.await?;
+            Ok(convert_error(response, fill_detail).await)
+        })
+    }
+}
+
+/// Convert the response into a `Problem` if it is an error that isn't one already.
+async fn convert_error(response: Response, fill_detail: bool) -> Response {
+    let status = response.status();
+    if !(status.is_client_error() || status.is_server_error()) || is_problem(&response) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+
+    let mut problem = crate::rejection::status_problem(status);
+    if fill_detail && is_text(&parts.headers) {
+        if let Ok(body) = axum::body::to_bytes(body, DETAIL_LIMIT).await {
+            let detail = String::from_utf8_lossy(&body);
+            if !detail.trim().is_empty() {
+                problem = problem.with_detail(detail.trim());
+            }
         }
     }
+
+    let (problem_parts, body) = problem.into_response().into_parts();
+    parts.headers.remove(CONTENT_TYPE);
+    parts.headers.remove(CONTENT_LENGTH);
+    parts.headers.extend(problem_parts.headers);
+    parts.extensions.extend(problem_parts.extensions);
+
+    Response::from_parts(parts, body)
+}
+
+/// Check if the response is already a problem.
+fn is_problem(response: &Response) -> bool {
+    response.extensions().get::<Problem>().is_some()
+        || response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| {
+                let content_type = content_type.to_ascii_lowercase();
+                content_type.starts_with("application/problem+json")
+                    || content_type.starts_with("application/problem+xml")
+            })
+}
+
+/// Check if the body of a response is text that can be used as the `detail`.
+fn is_text(headers: &http::HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .is_some_and(|content_type| content_type.to_ascii_lowercase().starts_with("text/plain"))
+}
+
+/// Create a router that serves the documentation page for every type in the registry, at the path
+/// of its URI. This can be merged into the router for the API.
+///
+/// Types whose URI is not an HTTP URI or a path, such as `tag:` or `urn:` URIs, are not served.
+///
+/// # Examples
+/// ```
+/// # use http::StatusCode;
+/// use problemdetails::ProblemTypeRegistry;
+///
+/// let registry = ProblemTypeRegistry::new().with_type(
+///     "/probs/out-of-credit",
+///     StatusCode::FORBIDDEN,
+///     "You do not have enough credit.",
+///     "The account does not have enough credit to pay for the request.",
+/// );
+///
+/// let router: axum::Router =
+///     axum::Router::new().merge(problemdetails::axum::documentation_router(&registry));
+/// ```
+pub fn documentation_router<S>(registry: &ProblemTypeRegistry) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    registry
+        .documentation_pages()
+        .into_iter()
+        .fold(Router::new(), |router, (path, page)| {
+            router.route(&path, get(move || async move { Html(page) }))
+        })
 }
 
 #[derive(Debug, Clone)]
 pub struct PanicHandlerBuilder {
     fill_detail: bool,
-    problem: Problem,
+    problem:     Problem,
 }
 
-/// Create a builder for [`tower_http::catch_panic:CatchPanicLayer`] which transforms panics into RFC-7807-compatible responses.
+/// Create a builder for [`tower_http::catch_panic:CatchPanicLayer`] which transforms panics into
+/// RFC-7807-compatible responses.
 impl PanicHandlerBuilder {
     pub fn new() -> Self {
         Self {
-            fill_detail: cfg!(debug_assertions),
-            problem: crate::new(StatusCode::INTERNAL_SERVER_ERROR)
-                .with_title("Internal server error"),
+            fill_detail: crate::detail::fill_detail(),
+            problem:     crate::new(StatusCode::INTERNAL_SERVER_ERROR)
+                .with_title(crate::INTERNAL_SERVER_ERROR_TITLE),
         }
     }
 
     /// Enable automatic setting of the `Problem` `detail` field to the panic message.
-    /// By default it is `true` for debug builds and `false` for release builds.
+    /// By default it is the value of [`crate::set_fill_detail`] when the builder is created.
     pub fn with_fill_detail(mut self, enabled: bool) -> Self {
         self.fill_detail = enabled;
         return self;
@@ -72,26 +435,17 @@ impl ResponseForPanic for PanicHandlerBuilder {
         &mut self,
         err: Box<dyn Any + Send + 'static>,
     ) -> http::Response<Self::ResponseBody> {
-        let detail = if let Some(s) = err.downcast_ref::<String>() {
-            s.clone()
-        } else if let Some(s) = err.downcast_ref::<&str>() {
-            s.to_string()
-        } else {
-            "Internal server error".to_string()
-        };
-
-        if self.fill_detail {
-            self.problem.clone().with_detail(detail).into_response()
-        } else {
-            self.problem.clone().into_response()
-        }
+        crate::panic::panic_problem(&self.problem, self.fill_detail, &*err).into_response()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use assert2::check;
-    use http::{header::CONTENT_TYPE, StatusCode};
+    use http::{
+        header::{ACCEPT, CONTENT_TYPE},
+        StatusCode,
+    };
     use insta::assert_json_snapshot;
     use serde_json::{json, Value};
 
@@ -111,6 +465,28 @@ mod tests {
         check!(response.text() == "");
     }
 
+    #[tokio::test]
+    async fn about_blank() {
+        let router = axum::Router::new().route(
+            "/test",
+            axum::routing::get(|| async {
+                crate::new(StatusCode::NOT_FOUND).with_about_blank(crate::AboutBlank::FillTitle)
+            }),
+        );
+
+        let test_server = axum_test::TestServer::new(router);
+
+        let response = test_server.get("/test").await;
+
+        check!(response.status_code() == StatusCode::NOT_FOUND);
+        check!(response.header(CONTENT_TYPE) == "application/problem+json");
+        assert_json_snapshot!(response.json::<Value>(), @r###"
+        {
+          "title": "Not Found"
+        }
+        "###);
+    }
+
     #[tokio::test]
     async fn default_panic() {
         let router = axum::Router::new()
@@ -246,6 +622,35 @@ mod tests {
         "###);
     }
 
+    #[tokio::test]
+    async fn insert_status_member() {
+        let router: axum::Router = axum::Router::new().route(
+            "/test",
+            axum::routing::get(|| async {
+                crate::new(StatusCode::NOT_FOUND)
+                    .with_title("Not Found")
+                    .with_value("status", 500)
+                    .with_status_member(crate::StatusMember::Insert)
+            }),
+        );
+
+        let test_server = axum_test::TestServer::new(router);
+
+        let response = test_server.get("/test").await;
+
+        check!(response.status_code() == StatusCode::NOT_FOUND);
+        check!(response.header(CONTENT_TYPE) == "application/problem+json");
+
+        let body: Value = response.json();
+
+        assert_json_snapshot!(body, @r###"
+        {
+          "status": 404,
+          "title": "Not Found"
+        }
+        "###);
+    }
+
     #[tokio::test]
     async fn rfc7807_validation_example() {
         let router: axum::Router = axum::Router::new().route(
@@ -293,4 +698,348 @@ mod tests {
         }
         "###);
     }
+
+    fn negotiate_router(negotiator: crate::Negotiator) -> axum_test::TestServer {
+        let router: axum::Router = axum::Router::new()
+            .route(
+                "/test",
+                axum::routing::get(|| async {
+                    crate::new(StatusCode::NOT_FOUND)
+                        .with_title("Not Found")
+                        .with_detail("No user <1>")
+                }),
+            )
+            .route(
+                "/empty",
+                axum::routing::get(|| async { crate::new(StatusCode::NOT_FOUND) }),
+            )
+            .route("/other", axum::routing::get(|| async { "Hello" }))
+            .layer(super::NegotiateLayer::new(negotiator));
+
+        axum_test::TestServer::new(router)
+    }
+
+    #[tokio::test]
+    async fn negotiate_default() {
+        let test_server = negotiate_router(crate::Negotiator::new());
+
+        let response = test_server.get("/test").await;
+
+        check!(response.status_code() == StatusCode::NOT_FOUND);
+        check!(response.header(CONTENT_TYPE) == "application/problem+json");
+        assert_json_snapshot!(response.json::<Value>(), @r###"
+        {
+          "detail": "No user <1>",
+          "title": "Not Found"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn negotiate_text() {
+        let test_server = negotiate_router(crate::Negotiator::new());
+
+        let response = test_server
+            .get("/test")
+            .add_header(ACCEPT, "application/problem+json;q=0.5, text/plain")
+            .await;
+
+        check!(response.status_code() == StatusCode::NOT_FOUND);
+        check!(response.header(CONTENT_TYPE) == "text/plain; charset=utf-8");
+        check!(response.text() == "404 Not Found\ndetail: No user <1>\ntitle: Not Found\n");
+    }
+
+    #[tokio::test]
+    async fn negotiate_html() {
+        let test_server = negotiate_router(crate::Negotiator::new());
+
+        let response = test_server
+            .get("/test")
+            .add_header(
+                ACCEPT,
+                "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
+            )
+            .await;
+
+        check!(response.status_code() == StatusCode::NOT_FOUND);
+        check!(response.header(CONTENT_TYPE) == "text/html; charset=utf-8");
+        check!(response.text().contains("<dd>No user &lt;1&gt;</dd>"));
+    }
+
+    #[cfg(feature = "xml")]
+    #[tokio::test]
+    async fn negotiate_xml() {
+        let test_server = negotiate_router(crate::Negotiator::new());
+
+        let response = test_server
+            .get("/test")
+            .add_header(ACCEPT, "application/problem+xml")
+            .await;
+
+        check!(response.status_code() == StatusCode::NOT_FOUND);
+        check!(response.header(CONTENT_TYPE) == "application/problem+xml");
+        check!(response.text().contains("<status>404</status>"));
+    }
+
+    #[tokio::test]
+    async fn negotiate_fallback() {
+        let test_server =
+            negotiate_router(crate::Negotiator::new().with_fallback(crate::Format::Text));
+
+        let response = test_server
+            .get("/test")
+            .add_header(ACCEPT, "image/png")
+            .await;
+
+        check!(response.header(CONTENT_TYPE) == "text/plain; charset=utf-8");
+    }
+
+    #[tokio::test]
+    async fn negotiate_json_compatibility() {
+        let test_server = negotiate_router(crate::Negotiator::new().with_json_compatibility(true));
+
+        let response = test_server
+            .get("/test")
+            .add_header(ACCEPT, "application/json")
+            .await;
+
+        check!(response.header(CONTENT_TYPE) == "application/json");
+        assert_json_snapshot!(response.json::<Value>(), @r###"
+        {
+          "detail": "No user <1>",
+          "title": "Not Found"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn negotiate_ignores_other_responses() {
+        let test_server = negotiate_router(crate::Negotiator::new());
+
+        let response = test_server
+            .get("/empty")
+            .add_header(ACCEPT, "text/plain")
+            .await;
+        check!(response.status_code() == StatusCode::NOT_FOUND);
+        check!(response.headers().get(CONTENT_TYPE) == None);
+        check!(response.text() == "");
+
+        let response = test_server
+            .get("/other")
+            .add_header(ACCEPT, "text/html")
+            .await;
+        check!(response.header(CONTENT_TYPE) == "text/plain; charset=utf-8");
+        check!(response.text() == "Hello");
+    }
+
+    #[tokio::test]
+    async fn documentation_router() {
+        let registry = crate::ProblemTypeRegistry::new()
+            .with_type(
+                "https://example.com/probs/out-of-credit",
+                StatusCode::FORBIDDEN,
+                "You do not have enough credit.",
+                "The account does not have enough credit to pay for the request.",
+            )
+            .with_type(
+                "tag:example.com,2026:conflict",
+                StatusCode::CONFLICT,
+                "Conflict",
+                "Somebody else changed it first.",
+            );
+        let router: axum::Router = super::documentation_router(&registry);
+
+        let test_server = axum_test::TestServer::new(router);
+
+        let response = test_server.get("/probs/out-of-credit").await;
+
+        check!(response.status_code() == StatusCode::OK);
+        check!(response.header(CONTENT_TYPE) == "text/html; charset=utf-8");
+        check!(
+            response.text()
+                == registry
+                    .get("https://example.com/probs/out-of-credit")
+                    .unwrap()
+                    .documentation()
+        );
+
+        let response = test_server.get("/probs/unknown").await;
+        check!(response.status_code() == StatusCode::NOT_FOUND);
+    }
+
+    fn empty_problem_router(policy: crate::EmptyProblem) -> axum_test::TestServer {
+        let router: axum::Router = axum::Router::new()
+            .route(
+                "/empty",
+                axum::routing::get(|| async { crate::new(StatusCode::NOT_FOUND) }),
+            )
+            .route(
+                "/full",
+                axum::routing::get(|| async {
+                    crate::new(StatusCode::CONFLICT).with_title("Conflict")
+                }),
+            )
+            .layer(super::EmptyProblemLayer::new(policy))
+            .layer(super::NegotiateLayer::default());
+
+        axum_test::TestServer::new(router)
+    }
+
+    #[tokio::test]
+    async fn empty_problem_status_only() {
+        let test_server = empty_problem_router(crate::EmptyProblem::StatusOnly);
+
+        let response = test_server.get("/empty").await;
+
+        check!(response.status_code() == StatusCode::NOT_FOUND);
+        check!(response.headers().get(CONTENT_TYPE) == None);
+        check!(response.text() == "");
+    }
+
+    #[tokio::test]
+    async fn empty_problem_empty_object() {
+        let test_server = empty_problem_router(crate::EmptyProblem::EmptyObject);
+
+        let response = test_server.get("/empty").await;
+
+        check!(response.status_code() == StatusCode::NOT_FOUND);
+        check!(response.header(CONTENT_TYPE) == "application/problem+json");
+        check!(response.text() == "{}");
+    }
+
+    #[tokio::test]
+    async fn empty_problem_about_blank() {
+        let test_server = empty_problem_router(crate::EmptyProblem::AboutBlank);
+
+        let response = test_server.get("/empty").await;
+
+        check!(response.status_code() == StatusCode::NOT_FOUND);
+        check!(response.header(CONTENT_TYPE) == "application/problem+json");
+        check!(response.text() == r#"{"title":"Not Found"}"#);
+
+        let response = test_server
+            .get("/empty")
+            .add_header(ACCEPT, "text/plain")
+            .await;
+
+        check!(response.header(CONTENT_TYPE) == "text/plain; charset=utf-8");
+        check!(response.text() == "404 Not Found\ntitle: Not Found\n");
+    }
+
+    #[tokio::test]
+    async fn empty_problem_ignores_full_problems() {
+        let test_server = empty_problem_router(crate::EmptyProblem::AboutBlank);
+
+        let response = test_server.get("/full").await;
+
+        check!(response.status_code() == StatusCode::CONFLICT);
+        check!(response.text() == r#"{"title":"Conflict"}"#);
+    }
+
+    fn convert_errors_router(layer: super::ConvertErrorsLayer) -> axum_test::TestServer {
+        let router: axum::Router = axum::Router::new()
+            .route(
+                "/json",
+                axum::routing::post(|_: axum::Json<Value>| async { "OK" }),
+            )
+            .route(
+                "/text",
+                axum::routing::get(|| async { (StatusCode::BAD_REQUEST, "Missing the name") }),
+            )
+            .route(
+                "/problem",
+                axum::routing::get(|| async {
+                    crate::new(StatusCode::CONFLICT).with_title("Already exists")
+                }),
+            )
+            .route("/ok", axum::routing::get(|| async { "OK" }))
+            .layer(layer);
+
+        axum_test::TestServer::new(router)
+    }
+
+    #[tokio::test]
+    async fn convert_unmatched_route() {
+        let test_server = convert_errors_router(super::ConvertErrorsLayer::new());
+
+        let response = test_server.get("/unknown").await;
+
+        check!(response.status_code() == StatusCode::NOT_FOUND);
+        check!(response.header(CONTENT_TYPE) == "application/problem+json");
+        assert_json_snapshot!(response.json::<Value>(), @r###"
+        {
+          "title": "Not Found"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn convert_method_not_allowed() {
+        let test_server = convert_errors_router(super::ConvertErrorsLayer::new());
+
+        let response = test_server.delete("/ok").await;
+
+        check!(response.status_code() == StatusCode::METHOD_NOT_ALLOWED);
+        check!(response.header(CONTENT_TYPE) == "application/problem+json");
+        check!(response.header(http::header::ALLOW) == "GET,HEAD");
+        assert_json_snapshot!(response.json::<Value>(), @r###"
+        {
+          "title": "Method Not Allowed"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn convert_rejection_with_detail() {
+        let test_server =
+            convert_errors_router(super::ConvertErrorsLayer::new().with_fill_detail(true));
+
+        let response = test_server
+            .post("/json")
+            .text("{")
+            .content_type("application/json")
+            .await;
+
+        check!(response.status_code() == StatusCode::BAD_REQUEST);
+        check!(response.header(CONTENT_TYPE) == "application/problem+json");
+        assert_json_snapshot!(response.json::<Value>(), @r###"
+        {
+          "detail": "Failed to parse the request body as JSON: EOF while parsing an object at line 1 column 1",
+          "title": "Bad Request"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn convert_without_detail() {
+        let test_server =
+            convert_errors_router(super::ConvertErrorsLayer::new().with_fill_detail(false));
+
+        let response = test_server.get("/text").await;
+
+        check!(response.status_code() == StatusCode::BAD_REQUEST);
+        assert_json_snapshot!(response.json::<Value>(), @r###"
+        {
+          "title": "Bad Request"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn convert_ignores_problems_and_success() {
+        let test_server =
+            convert_errors_router(super::ConvertErrorsLayer::new().with_fill_detail(true));
+
+        let response = test_server.get("/problem").await;
+        check!(response.status_code() == StatusCode::CONFLICT);
+        assert_json_snapshot!(response.json::<Value>(), @r###"
+        {
+          "title": "Already exists"
+        }
+        "###);
+
+        let response = test_server.get("/ok").await;
+        check!(response.status_code() == StatusCode::OK);
+        check!(response.text() == "OK");
+    }
 }
@@ -0,0 +1,252 @@
+use std::ops::{Deref, DerefMut};
+
+use axum::{
+    extract::{
+        rejection::{JsonRejection, PathRejection, QueryRejection},
+        FromRequest, FromRequestParts, Request,
+    },
+    response::{IntoResponse, Response},
+};
+use http::request::Parts;
+use serde::Serialize;
+
+use crate::Problem;
+
+/// Extractor and response for JSON bodies, the same as `axum::Json` except that it rejects the
+/// request with a `Problem`.
+///
+/// The rejections are converted as described in the [`rejection`](super::rejection) module.
+///
+/// # Examples
+/// ```
+/// use problemdetails::axum::Json;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct CreateUser {
+///     name: String,
+/// }
+///
+/// async fn create_user(Json(user): Json<CreateUser>) -> String {
+///     user.name
+/// }
+///
+/// let router: axum::Router =
+///     axum::Router::new().route("/users", axum::routing::post(create_user));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json<T>(pub T);
+
+impl<T, S> FromRequest<S> for Json<T>
+where
+    axum::Json<T>: FromRequest<S, Rejection = JsonRejection>,
+    S: Send + Sync,
+{
+    type Rejection = Problem;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::Json(value) = axum::Json::from_request(request, state).await?;
+
+        Ok(Self(value))
+    }
+}
+
+impl<T> IntoResponse for Json<T>
+where
+    T: Serialize,
+{
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+impl<T> Deref for Json<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Json<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Extractor for the query string, the same as `axum::extract::Query` except that it rejects the
+/// request with a `Problem`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Query<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    axum::extract::Query<T>: FromRequestParts<S, Rejection = QueryRejection>,
+    S: Send + Sync,
+{
+    type Rejection = Problem;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::extract::Query(value) =
+            axum::extract::Query::from_request_parts(parts, state).await?;
+
+        Ok(Self(value))
+    }
+}
+
+impl<T> Deref for Query<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Query<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Extractor for the path parameters, the same as `axum::extract::Path` except that it rejects
+/// the request with a `Problem`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Path<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for Path<T>
+where
+    axum::extract::Path<T>: FromRequestParts<S, Rejection = PathRejection>,
+    S: Send + Sync,
+{
+    type Rejection = Problem;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::extract::Path(value) =
+            axum::extract::Path::from_request_parts(parts, state).await?;
+
+        Ok(Self(value))
+    }
+}
+
+impl<T> Deref for Path<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Path<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use axum::routing::{get, post};
+    use http::{header::CONTENT_TYPE, StatusCode};
+    use insta::assert_json_snapshot;
+    use serde::Deserialize;
+    use serde_json::Value;
+
+    use super::{Json, Path, Query};
+
+    #[derive(Debug, Deserialize, serde::Serialize)]
+    struct User {
+        name: String,
+    }
+
+    fn test_server() -> axum_test::TestServer {
+        let router = axum::Router::new()
+            .route("/json", post(|Json(user): Json<User>| async { Json(user) }))
+            .route(
+                "/query",
+                get(|Query(user): Query<User>| async { user.name }),
+            )
+            .route(
+                "/path/{id}",
+                get(|Path(id): Path<u32>| async move { id.to_string() }),
+            );
+
+        axum_test::TestServer::new(router)
+    }
+
+    #[tokio::test]
+    async fn json() {
+        let response = test_server()
+            .post("/json")
+            .json(&serde_json::json!({"name": "Alice"}))
+            .await;
+
+        check!(response.status_code() == StatusCode::OK);
+        check!(response.header(CONTENT_TYPE) == "application/json");
+        check!(response.json::<Value>()["name"] == "Alice");
+    }
+
+    #[tokio::test]
+    async fn json_rejection() {
+        let response = test_server()
+            .post("/json")
+            .json(&serde_json::json!({"id": 1}))
+            .expect_failure()
+            .await;
+
+        check!(response.status_code() == StatusCode::UNPROCESSABLE_ENTITY);
+        check!(response.header(CONTENT_TYPE) == "application/problem+json");
+        assert_json_snapshot!(response.json::<Value>(), @r###"
+        {
+          "detail": "Failed to deserialize the JSON body into the target type: missing field `name` at line 1 column 8",
+          "title": "Invalid JSON data",
+          "type": "tag:sazzer.github.io,2026:problemdetails/json-data-error"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn query() {
+        let response = test_server().get("/query?name=Bob").await;
+
+        check!(response.status_code() == StatusCode::OK);
+        check!(response.text() == "Bob");
+    }
+
+    #[tokio::test]
+    async fn query_rejection() {
+        let response = test_server().get("/query").expect_failure().await;
+
+        check!(response.status_code() == StatusCode::BAD_REQUEST);
+        check!(response.header(CONTENT_TYPE) == "application/problem+json");
+        assert_json_snapshot!(response.json::<Value>(), @r###"
+        {
+          "detail": "Failed to deserialize query string: missing field `name`",
+          "title": "Invalid query string",
+          "type": "tag:sazzer.github.io,2026:problemdetails/invalid-query-string"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn path() {
+        let response = test_server().get("/path/42").await;
+
+        check!(response.status_code() == StatusCode::OK);
+        check!(response.text() == "42");
+    }
+
+    #[tokio::test]
+    async fn path_rejection() {
+        let response = test_server().get("/path/abc").expect_failure().await;
+
+        check!(response.status_code() == StatusCode::BAD_REQUEST);
+        check!(response.header(CONTENT_TYPE) == "application/problem+json");
+        assert_json_snapshot!(response.json::<Value>(), @r###"
+        {
+          "detail": "Invalid URL: Cannot parse `abc` to a `u32`",
+          "title": "Invalid path parameters",
+          "type": "tag:sazzer.github.io,2026:problemdetails/invalid-path-params"
+        }
+        "###);
+    }
+}
@@ -0,0 +1,393 @@
+//! Conversion of the rejections from Axum's extractors into a `Problem`.
+//!
+//! Every rejection is converted into a problem with a stable "type" URI, which is one of the
+//! [`ProblemType`] values in this module, so that clients can tell the kinds of rejection apart.
+//! The types for errors that other HTTP Servers have too are shared from [`crate::types`].
+//!
+//! The status code of the problem is the same as the status code Axum would have used, and the
+//! "detail" is the text that Axum would have sent as the body. Some rejections describe the handler
+//! rather than the request, such as [`INVALID_PATH_PARAMS`] when the target type doesn't match the
+//! route, and are sent as a 5xx status code. For these, the text is only used if enabled with
+//! [`set_fill_detail`](crate::set_fill_detail).
+//!
+//! # Examples
+//! ```
+//! use axum::extract::rejection::JsonRejection;
+//! use problemdetails::{axum::rejection, Problem};
+//!
+//! async fn handler(
+//!     body: Result<axum::Json<serde_json::Value>, JsonRejection>,
+//! ) -> Result<String, Problem> {
+//!     let axum::Json(body) = body.map_err(Problem::from)?;
+//!
+//!     Ok(body.to_string())
+//! }
+//!
+//! fn is_bad_json(problem: &Problem) -> bool {
+//!     problem.is(&rejection::JSON_SYNTAX_ERROR) || problem.is(&rejection::JSON_DATA_ERROR)
+//! }
+//! ```
+
+use axum::extract::rejection::{
+    BytesRejection, ExtensionRejection, FailedToBufferBody, FormRejection, JsonRejection,
+    MatchedPathRejection, NestedPathRejection, PathRejection, QueryRejection, RawFormRejection,
+    RawPathParamsRejection, StringRejection,
+};
+use http::StatusCode;
+
+pub use crate::types::{
+    BODY_TOO_LARGE, FAILED_TO_READ_BODY, INVALID_FORM_BODY, INVALID_PATH_PARAMS,
+    INVALID_QUERY_STRING, JSON_DATA_ERROR, JSON_SYNTAX_ERROR, MISSING_JSON_CONTENT_TYPE,
+};
+use crate::{Problem, ProblemType};
+
+/// A path parameter was not valid UTF-8 after being percent-decoded.
+pub const INVALID_UTF8_IN_PATH_PARAM: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/axum/invalid-utf8-in-path-param",
+    StatusCode::BAD_REQUEST,
+    "Invalid path parameter",
+);
+
+/// The path parameters were extracted from a route that doesn't have any.
+pub const MISSING_PATH_PARAMS: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/axum/missing-path-params",
+    StatusCode::INTERNAL_SERVER_ERROR,
+    "Missing path parameters",
+);
+
+/// The request didn't have a `Content-Type` of `application/x-www-form-urlencoded`.
+pub const INVALID_FORM_CONTENT_TYPE: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/axum/invalid-form-content-type",
+    StatusCode::UNSUPPORTED_MEDIA_TYPE,
+    "Expected a form request body",
+);
+
+/// The form in the query string couldn't be deserialized into the target type.
+pub const INVALID_FORM: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/axum/invalid-form",
+    StatusCode::BAD_REQUEST,
+    "Invalid form",
+);
+
+/// The request body was not valid UTF-8.
+pub const INVALID_UTF8_BODY: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/axum/invalid-utf8-body",
+    StatusCode::BAD_REQUEST,
+    "Invalid request body",
+);
+
+/// A request extension that the handler needs is missing.
+pub const MISSING_EXTENSION: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/axum/missing-extension",
+    StatusCode::INTERNAL_SERVER_ERROR,
+    "Missing request extension",
+);
+
+/// The matched path was extracted from a request that didn't match a route.
+pub const MATCHED_PATH_MISSING: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/axum/matched-path-missing",
+    StatusCode::INTERNAL_SERVER_ERROR,
+    "No matched path",
+);
+
+/// The nested path was extracted from a route that isn't nested.
+pub const NOT_NESTED: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/axum/not-nested",
+    StatusCode::INTERNAL_SERVER_ERROR,
+    "Route is not nested",
+);
+
+/// Build the problem for a rejection, keeping the status code and body text from Axum.
+///
+/// The rejection enums are non-exhaustive, so rejections that Axum adds in the future are
+/// converted without a "type".
+fn problem(problem_type: Option<&ProblemType>, status: StatusCode, body_text: String) -> Problem {
+    crate::rejection::rejection_problem(
+        problem_type,
+        status,
+        body_text,
+        crate::detail::fill_detail(),
+    )
+}
+
+/// Get the problem type for a rejection when buffering the request body.
+fn failed_to_buffer_body_type(rejection: &FailedToBufferBody) -> Option<&'static ProblemType> {
+    match rejection {
+        FailedToBufferBody::LengthLimitError(_) => Some(&BODY_TOO_LARGE),
+        FailedToBufferBody::UnknownBodyError(_) => Some(&FAILED_TO_READ_BODY),
+        _ => None,
+    }
+}
+
+/// Get the problem type for a rejection when reading the request body as bytes.
+fn bytes_type(rejection: &BytesRejection) -> Option<&'static ProblemType> {
+    match rejection {
+        BytesRejection::FailedToBufferBody(rejection) => failed_to_buffer_body_type(rejection),
+        _ => None,
+    }
+}
+
+impl From<JsonRejection> for Problem {
+    fn from(rejection: JsonRejection) -> Self {
+        let problem_type = match &rejection {
+            JsonRejection::JsonDataError(_) => Some(&JSON_DATA_ERROR),
+            JsonRejection::JsonSyntaxError(_) => Some(&JSON_SYNTAX_ERROR),
+            JsonRejection::MissingJsonContentType(_) => Some(&MISSING_JSON_CONTENT_TYPE),
+            JsonRejection::BytesRejection(rejection) => bytes_type(rejection),
+            _ => None,
+        };
+
+        problem(problem_type, rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for Problem {
+    fn from(rejection: QueryRejection) -> Self {
+        let problem_type = match &rejection {
+            QueryRejection::FailedToDeserializeQueryString(_) => Some(&INVALID_QUERY_STRING),
+            _ => None,
+        };
+
+        problem(problem_type, rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<PathRejection> for Problem {
+    fn from(rejection: PathRejection) -> Self {
+        let problem_type = match &rejection {
+            PathRejection::FailedToDeserializePathParams(_) => Some(&INVALID_PATH_PARAMS),
+            PathRejection::MissingPathParams(_) => Some(&MISSING_PATH_PARAMS),
+            _ => None,
+        };
+
+        problem(problem_type, rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<RawPathParamsRejection> for Problem {
+    fn from(rejection: RawPathParamsRejection) -> Self {
+        let problem_type = match &rejection {
+            RawPathParamsRejection::InvalidUtf8InPathParam(_) => Some(&INVALID_UTF8_IN_PATH_PARAM),
+            RawPathParamsRejection::MissingPathParams(_) => Some(&MISSING_PATH_PARAMS),
+            _ => None,
+        };
+
+        problem(problem_type, rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<FormRejection> for Problem {
+    fn from(rejection: FormRejection) -> Self {
+        let problem_type = match &rejection {
+            FormRejection::InvalidFormContentType(_) => Some(&INVALID_FORM_CONTENT_TYPE),
+            FormRejection::FailedToDeserializeForm(_) => Some(&INVALID_FORM),
+            FormRejection::FailedToDeserializeFormBody(_) => Some(&INVALID_FORM_BODY),
+            FormRejection::BytesRejection(rejection) => bytes_type(rejection),
+            _ => None,
+        };
+
+        problem(problem_type, rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<RawFormRejection> for Problem {
+    fn from(rejection: RawFormRejection) -> Self {
+        let problem_type = match &rejection {
+            RawFormRejection::InvalidFormContentType(_) => Some(&INVALID_FORM_CONTENT_TYPE),
+            RawFormRejection::BytesRejection(rejection) => bytes_type(rejection),
+            _ => None,
+        };
+
+        problem(problem_type, rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<BytesRejection> for Problem {
+    fn from(rejection: BytesRejection) -> Self {
+        problem(
+            bytes_type(&rejection),
+            rejection.status(),
+            rejection.body_text(),
+        )
+    }
+}
+
+impl From<StringRejection> for Problem {
+    fn from(rejection: StringRejection) -> Self {
+        let problem_type = match &rejection {
+            StringRejection::FailedToBufferBody(rejection) => failed_to_buffer_body_type(rejection),
+            StringRejection::InvalidUtf8(_) => Some(&INVALID_UTF8_BODY),
+            _ => None,
+        };
+
+        problem(problem_type, rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<ExtensionRejection> for Problem {
+    fn from(rejection: ExtensionRejection) -> Self {
+        let problem_type = match &rejection {
+            ExtensionRejection::MissingExtension(_) => Some(&MISSING_EXTENSION),
+            _ => None,
+        };
+
+        problem(problem_type, rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<MatchedPathRejection> for Problem {
+    fn from(rejection: MatchedPathRejection) -> Self {
+        let problem_type = match &rejection {
+            MatchedPathRejection::MatchedPathMissing(_) => Some(&MATCHED_PATH_MISSING),
+            _ => None,
+        };
+
+        problem(problem_type, rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<NestedPathRejection> for Problem {
+    fn from(rejection: NestedPathRejection) -> Self {
+        problem(Some(&NOT_NESTED), rejection.status(), rejection.body_text())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use axum::{
+        extract::{
+            rejection::{ExtensionRejection, FormRejection, JsonRejection, PathRejection},
+            Extension, Form, FromRequest, Path,
+        },
+        http::{Request, StatusCode},
+        routing::{get, post},
+        Router,
+    };
+    use insta::assert_json_snapshot;
+    use serde_json::Value;
+
+    use super::{
+        INVALID_FORM_CONTENT_TYPE, INVALID_PATH_PARAMS, JSON_DATA_ERROR, JSON_SYNTAX_ERROR,
+        MISSING_EXTENSION, MISSING_JSON_CONTENT_TYPE,
+    };
+    use crate::Problem;
+
+    async fn json_rejection(content_type: &str, body: &'static str) -> JsonRejection {
+        let request = Request::builder()
+            .header("content-type", content_type)
+            .body(axum::body::Body::from(body))
+            .unwrap();
+
+        axum::Json::<Vec<u32>>::from_request(request, &())
+            .await
+            .unwrap_err()
+    }
+
+    #[tokio::test]
+    async fn json_syntax_error() {
+        let problem = Problem::from(json_rejection("application/json", "[1,").await);
+
+        check!(problem.is(&JSON_SYNTAX_ERROR));
+        check!(problem.status_code == StatusCode::BAD_REQUEST);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "detail": "Failed to parse the request body as JSON: EOF while parsing a value at line 1 column 3",
+          "title": "Invalid JSON syntax",
+          "type": "tag:sazzer.github.io,2026:problemdetails/json-syntax-error"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn json_data_error() {
+        let problem = Problem::from(json_rejection("application/json", r#"["a"]"#).await);
+
+        check!(problem.is(&JSON_DATA_ERROR));
+        check!(problem.status_code == StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn missing_json_content_type() {
+        let problem = Problem::from(json_rejection("text/plain", "[1]").await);
+
+        check!(problem.is(&MISSING_JSON_CONTENT_TYPE));
+        check!(problem.status_code == StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        check!(problem.detail() == Some("Expected request with `Content-Type: application/json`"));
+    }
+
+    async fn get_json(router: Router, uri: &str) -> (StatusCode, Value) {
+        let test_server = axum_test::TestServer::new(router);
+        let response = test_server.get(uri).expect_failure().await;
+
+        (response.status_code(), response.json())
+    }
+
+    #[tokio::test]
+    async fn path_params() {
+        let router = Router::new().route(
+            "/{id}",
+            get(|id: Result<Path<u32>, PathRejection>| async move {
+                id.map(|Path(id)| id.to_string()).map_err(Problem::from)
+            }),
+        );
+
+        let (status, body) = get_json(router, "/abc").await;
+
+        check!(status == StatusCode::BAD_REQUEST);
+        check!(body["type"] == INVALID_PATH_PARAMS.uri());
+        check!(body["title"] == "Invalid path parameters");
+    }
+
+    #[tokio::test]
+    async fn wrong_number_of_path_params() {
+        let router = Router::new().route(
+            "/{a}/{b}",
+            get(|id: Result<Path<u32>, PathRejection>| async move {
+                id.map(|Path(id)| id.to_string()).map_err(Problem::from)
+            }),
+        );
+
+        let (status, body) = get_json(router, "/1/2").await;
+
+        check!(status == StatusCode::INTERNAL_SERVER_ERROR);
+        check!(body["type"] == INVALID_PATH_PARAMS.uri());
+    }
+
+    #[tokio::test]
+    async fn invalid_form_content_type() {
+        let router = Router::new().route(
+            "/",
+            post(
+                |form: Result<Form<Vec<(String, String)>>, FormRejection>| async move {
+                    form.map(|_| "").map_err(Problem::from)
+                },
+            ),
+        );
+
+        let test_server = axum_test::TestServer::new(router);
+        let response = test_server.post("/").text("a=b").expect_failure().await;
+
+        check!(response.status_code() == StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        check!(response.json::<Value>()["type"] == INVALID_FORM_CONTENT_TYPE.uri());
+    }
+
+    #[tokio::test]
+    async fn missing_extension() {
+        let router = Router::new().route(
+            "/",
+            get(
+                |extension: Result<Extension<String>, ExtensionRejection>| async move {
+                    extension
+                        .map(|Extension(value)| value)
+                        .map_err(Problem::from)
+                },
+            ),
+        );
+
+        let (status, body) = get_json(router, "/").await;
+
+        check!(status == StatusCode::INTERNAL_SERVER_ERROR);
+        check!(body["type"] == MISSING_EXTENSION.uri());
+    }
+}
@@ -0,0 +1,221 @@
+use std::{collections::BTreeMap, fmt};
+
+use bytes::Bytes;
+use http::{header::CONTENT_TYPE, HeaderValue, StatusCode};
+use serde_json::Value;
+
+use super::Problem;
+
+/// Errors that can occur when reading a `Problem` from an HTTP response.
+#[derive(Debug)]
+pub enum FromResponseError {
+    /// The response did not have a `Content-Type` of `application/problem+json`.
+    ContentType(Option<HeaderValue>),
+    /// The response body could not be parsed as a JSON object.
+    Body(serde_json::Error),
+    /// The `status` member of the body does not match the HTTP status code of the response.
+    StatusMismatch {
+        /// The HTTP status code of the response.
+        status_code: StatusCode,
+        /// The value of the `status` member in the body.
+        status:      Value,
+    },
+}
+
+impl fmt::Display for FromResponseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ContentType(Some(content_type)) => {
+                write!(f, "unexpected content type: {content_type:?}")
+            },
+            Self::ContentType(None) => write!(f, "missing content type"),
+            Self::Body(err) => write!(f, "invalid problem body: {err}"),
+            Self::StatusMismatch {
+                status_code,
+                status,
+            } => write!(
+                f,
+                "status member {status} does not match the HTTP status code {}",
+                status_code.as_u16()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FromResponseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Body(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Problem {
+    /// Parse a `Problem` from an `application/problem+json` HTTP response.
+    ///
+    /// The `status_code` of the problem is taken from the HTTP status of the response. If the body
+    /// also has a `status` member then it must agree with this.
+    ///
+    /// # Errors
+    /// If the response is not `application/problem+json`, if the body is not a JSON object, or if
+    /// the `status` member of the body disagrees with the HTTP status code.
+    pub fn from_response(
+        response: http::Response<Bytes>,
+    ) -> std::result::Result<Self, FromResponseError> {
+        let (parts, body) = response.into_parts();
+
+        let content_type = parts.headers.get(CONTENT_TYPE);
+        if !content_type.is_some_and(is_problem_json) {
+            return Err(FromResponseError::ContentType(content_type.cloned()));
+        }
+
+        let status_code = parts.status;
+        let mut body: BTreeMap<String, Value> =
+            serde_json::from_slice(&body).map_err(FromResponseError::Body)?;
+
+        if let Some(status) = body.remove("status") {
+            if status.as_u64() != Some(u64::from(status_code.as_u16())) {
+                return Err(FromResponseError::StatusMismatch {
+                    status_code,
+                    status,
+                });
+            }
+        }
+
+        Ok(Self {
+            body,
+            ..crate::new(status_code)
+        })
+    }
+}
+
+/// Check if a `Content-Type` header is `application/problem+json`, ignoring any parameters.
+pub(crate) fn is_problem_json(content_type: &HeaderValue) -> bool {
+    content_type.to_str().is_ok_and(|content_type| {
+        content_type
+            .split(';')
+            .next()
+            .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/problem+json"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{assert, check};
+    use bytes::Bytes;
+    use http::{header::CONTENT_TYPE, StatusCode};
+    use insta::assert_json_snapshot;
+
+    use super::FromResponseError;
+    use crate::Problem;
+
+    fn response(
+        status: StatusCode,
+        content_type: &str,
+        body: &'static str,
+    ) -> http::Response<Bytes> {
+        http::Response::builder()
+            .status(status)
+            .header(CONTENT_TYPE, content_type)
+            .body(Bytes::from_static(body.as_bytes()))
+            .unwrap()
+    }
+
+    #[test]
+    fn rfc7807_forbidden_example() {
+        let problem = Problem::from_response(response(
+            StatusCode::FORBIDDEN,
+            "application/problem+json",
+            r#"{
+                "type": "https://example.com/probs/out-of-credit",
+                "title": "You do not have enough credit.",
+                "status": 403,
+                "detail": "Your current balance is 30, but that costs 50.",
+                "instance": "/account/12345/msgs/abc",
+                "balance": 30,
+                "accounts": ["/account/12345", "/account/67890"]
+            }"#,
+        ))
+        .unwrap();
+
+        check!(problem.status_code == StatusCode::FORBIDDEN);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "accounts": [
+            "/account/12345",
+            "/account/67890"
+          ],
+          "balance": 30,
+          "detail": "Your current balance is 30, but that costs 50.",
+          "instance": "/account/12345/msgs/abc",
+          "title": "You do not have enough credit.",
+          "type": "https://example.com/probs/out-of-credit"
+        }
+        "###);
+    }
+
+    #[test]
+    fn status_from_response() {
+        let problem = Problem::from_response(response(
+            StatusCode::NOT_FOUND,
+            "Application/Problem+JSON; charset=utf-8",
+            r#"{"title": "Not Found"}"#,
+        ))
+        .unwrap();
+
+        check!(problem.status_code == StatusCode::NOT_FOUND);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "title": "Not Found"
+        }
+        "###);
+    }
+
+    #[test]
+    fn wrong_content_type() {
+        let result = Problem::from_response(response(
+            StatusCode::NOT_FOUND,
+            "application/json",
+            r#"{"title": "Not Found"}"#,
+        ));
+
+        assert!(let Err(FromResponseError::ContentType(Some(content_type))) = result);
+        check!(content_type == "application/json");
+    }
+
+    #[test]
+    fn missing_content_type() {
+        let result = Problem::from_response(
+            http::Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Bytes::new())
+                .unwrap(),
+        );
+
+        assert!(let Err(FromResponseError::ContentType(None)) = result);
+    }
+
+    #[test]
+    fn invalid_body() {
+        let result = Problem::from_response(response(
+            StatusCode::NOT_FOUND,
+            "application/problem+json",
+            r#"["Not Found"]"#,
+        ));
+
+        assert!(let Err(FromResponseError::Body(_)) = result);
+    }
+
+    #[test]
+    fn status_mismatch() {
+        let result = Problem::from_response(response(
+            StatusCode::NOT_FOUND,
+            "application/problem+json",
+            r#"{"status": 500}"#,
+        ));
+
+        assert!(let Err(err @ FromResponseError::StatusMismatch { .. }) = result);
+        check!(err.to_string() == "status member 500 does not match the HTTP status code 404");
+    }
+}
@@ -0,0 +1,271 @@
+pub use problemdetails_derive::IntoProblem;
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use assert2::check;
+    use http::StatusCode;
+    use insta::assert_json_snapshot;
+    use serde::Serialize;
+
+    use crate::{IntoProblem, Problem};
+
+    #[derive(Debug, Serialize)]
+    struct Link {
+        rel:  String,
+        href: String,
+    }
+
+    #[derive(IntoProblem)]
+    #[problem(type = "https://example.com/probs/accounts")]
+    enum AccountError {
+        #[problem(
+            status = 403,
+            type = "https://example.com/probs/out-of-credit",
+            title = "You do not have enough credit.",
+            detail = "Your current balance is {balance}, but that costs {cost}."
+        )]
+        OutOfCredit {
+            balance:  u32,
+            #[problem(skip)]
+            cost:     u32,
+            accounts: Vec<String>,
+        },
+        #[problem(status = 404, title = "Account not found", detail = "No account {0}")]
+        NotFound(#[problem(rename = "account")] String, u32),
+        #[problem(status = 409)]
+        Conflict {
+            #[problem(rename = "see")]
+            link: Link,
+        },
+        Unknown,
+    }
+
+    #[derive(IntoProblem)]
+    #[problem(status = 429, title = "Too many requests")]
+    struct RateLimited {
+        retry_after: u64,
+    }
+
+    #[test]
+    fn named_fields() {
+        let problem: Problem = AccountError::OutOfCredit {
+            balance:  30,
+            cost:     50,
+            accounts: vec!["/account/12345".to_owned(), "/account/67890".to_owned()],
+        }
+        .into();
+
+        check!(problem.status_code == StatusCode::FORBIDDEN);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "accounts": [
+            "/account/12345",
+            "/account/67890"
+          ],
+          "balance": 30,
+          "detail": "Your current balance is 30, but that costs 50.",
+          "title": "You do not have enough credit.",
+          "type": "https://example.com/probs/out-of-credit"
+        }
+        "###);
+    }
+
+    #[test]
+    fn tuple_fields() {
+        let problem: Problem = AccountError::NotFound("12345".to_owned(), 1).into();
+
+        check!(problem.status_code == StatusCode::NOT_FOUND);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "account": "12345",
+          "detail": "No account 12345",
+          "title": "Account not found",
+          "type": "https://example.com/probs/accounts"
+        }
+        "###);
+    }
+
+    #[test]
+    fn renamed_field() {
+        let problem: Problem = AccountError::Conflict {
+            link: Link {
+                rel:  "existing".to_owned(),
+                href: "/account/12345".to_owned(),
+            },
+        }
+        .into();
+
+        check!(problem.status_code == StatusCode::CONFLICT);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "see": {
+            "rel": "existing",
+            "href": "/account/12345"
+          },
+          "type": "https://example.com/probs/accounts"
+        }
+        "###);
+    }
+
+    #[test]
+    fn defaults() {
+        let problem: Problem = AccountError::Unknown.into();
+
+        check!(problem.status_code == StatusCode::INTERNAL_SERVER_ERROR);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "type": "https://example.com/probs/accounts"
+        }
+        "###);
+    }
+
+    #[test]
+    fn struct_type() {
+        let problem: Problem = RateLimited { retry_after: 30 }.into();
+
+        check!(problem.status_code == StatusCode::TOO_MANY_REQUESTS);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "retry_after": 30,
+          "title": "Too many requests"
+        }
+        "###);
+    }
+
+    #[derive(Debug, thiserror::Error, IntoProblem)]
+    enum StorageError {
+        #[error("Item {id} was not found")]
+        #[problem(status = 404, title = "Not Found")]
+        NotFound { id: u32 },
+        #[error("Item {0} has been deleted")]
+        #[problem(status = 410, detail = "Item {0} is gone")]
+        Deleted(#[problem(rename = "item")] u32),
+        #[error("Database connection to {host} failed")]
+        #[problem(status = 503, title = "Service Unavailable", skip_detail)]
+        Connection {
+            #[problem(skip)]
+            host:   String,
+            source: std::io::Error,
+        },
+        #[error("Query {query} failed on shard {shard}")]
+        #[problem(status = 503, title = "Service Unavailable", skip_detail)]
+        Query {
+            #[problem(skip)]
+            query: String,
+            shard: u32,
+        },
+        #[error("Could not read the item")]
+        Io(#[from] std::io::Error),
+    }
+
+    #[derive(Debug, thiserror::Error, IntoProblem)]
+    #[error("Token expired at {expired_at}")]
+    #[problem(status = 401)]
+    struct TokenExpired {
+        expired_at: String,
+    }
+
+    #[test]
+    fn thiserror_detail() {
+        let problem: Problem = StorageError::NotFound { id: 7 }.into();
+
+        check!(problem.status_code == StatusCode::NOT_FOUND);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "detail": "Item 7 was not found",
+          "id": 7,
+          "title": "Not Found"
+        }
+        "###);
+    }
+
+    #[test]
+    fn thiserror_explicit_detail() {
+        let problem: Problem = StorageError::Deleted(7).into();
+
+        check!(problem.status_code == StatusCode::GONE);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "detail": "Item 7 is gone",
+          "item": 7
+        }
+        "###);
+    }
+
+    #[test]
+    fn thiserror_skip_detail() {
+        let problem: Problem = StorageError::Connection {
+            host:   "db.internal:5432".to_owned(),
+            source: std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "refused"),
+        }
+        .into();
+
+        check!(problem.status_code == StatusCode::SERVICE_UNAVAILABLE);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "title": "Service Unavailable"
+        }
+        "###);
+    }
+
+    #[test]
+    fn thiserror_skip_detail_keeps_fields() {
+        let problem: Problem = StorageError::Query {
+            query: "SELECT * FROM users".to_owned(),
+            shard: 3,
+        }
+        .into();
+
+        check!(problem.status_code == StatusCode::SERVICE_UNAVAILABLE);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "shard": 3,
+          "title": "Service Unavailable"
+        }
+        "###);
+    }
+
+    #[test]
+    fn thiserror_source() {
+        let problem: Problem = StorageError::Io(std::io::Error::other("disk full")).into();
+
+        check!(problem.status_code == StatusCode::INTERNAL_SERVER_ERROR);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "detail": "Could not read the item"
+        }
+        "###);
+
+        let source = problem.source().unwrap();
+        check!(source.to_string() == "Could not read the item");
+        check!(source.source().map(ToString::to_string).as_deref() == Some("disk full"));
+    }
+
+    #[test]
+    fn thiserror_struct() {
+        let problem: Problem = TokenExpired {
+            expired_at: "2024-01-01T00:00:00Z".to_owned(),
+        }
+        .into();
+
+        check!(problem.status_code == StatusCode::UNAUTHORIZED);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "detail": "Token expired at 2024-01-01T00:00:00Z",
+          "expired_at": "2024-01-01T00:00:00Z"
+        }
+        "###);
+    }
+
+    #[test]
+    fn question_mark() {
+        fn fails() -> crate::Result<()> {
+            Err(AccountError::Unknown)?;
+            Ok(())
+        }
+
+        check!(fails().unwrap_err().status_code == StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,34 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether the messages of internal errors are used as the `detail` of server errors, as set by
+/// [`set_fill_detail`].
+static FILL_DETAIL: AtomicBool = AtomicBool::new(cfg!(debug_assertions));
+
+/// Enable setting the `detail` of server errors to the message of the internal error that caused
+/// them.
+///
+/// This applies to the default problem for anyhow and eyre reports, and to Axum's rejections that
+/// are sent as a 5xx status code. It is also the default for `ConvertErrorsLayer` and the
+/// `PanicHandlerBuilder` of each HTTP Server, which read it when they are created. By default it is
+/// `true` for debug builds and `false` for release builds, so that internal details are not leaked
+/// in release builds.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "anyhow")]
+/// # {
+/// problemdetails::set_fill_detail(false);
+///
+/// let problem = problemdetails::Problem::from(anyhow::anyhow!("Database password is hunter2"));
+/// assert_eq!(problem.detail(), None);
+/// # }
+/// ```
+pub fn set_fill_detail(enabled: bool) {
+    FILL_DETAIL.store(enabled, Ordering::Relaxed);
+}
+
+/// Check whether the messages of internal errors are used as the `detail` of server errors.
+#[allow(dead_code)] // This is used by the optional features.
+pub(crate) fn fill_detail() -> bool {
+    FILL_DETAIL.load(Ordering::Relaxed)
+}
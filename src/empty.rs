@@ -0,0 +1,93 @@
+use super::{AboutBlank, Problem};
+
+/// Policy for sending problems that have an empty body, such as
+/// `problemdetails::new(StatusCode::NOT_FOUND)`.
+///
+/// This is applied to a whole router by `axum::EmptyProblemLayer` or
+/// `poem::EmptyProblemMiddleware`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmptyProblem {
+    /// Send only the status code, without a body or `Content-Type`.
+    #[default]
+    StatusOnly,
+    /// Send `{}` as `application/problem+json`.
+    EmptyObject,
+    /// Send an `about:blank` problem, with the `title` filled in from the reason phrase of the
+    /// status code. This is sent as `{}` if the status code has no reason phrase.
+    AboutBlank,
+}
+
+impl EmptyProblem {
+    /// Get the problem and the JSON body to send for a problem with an empty body, or `None` if it
+    /// should be sent without a body.
+    #[allow(dead_code)] // This is used by the server features.
+    pub(crate) fn fill(self, problem: &Problem) -> Option<(Problem, String)> {
+        let problem = match self {
+            Self::StatusOnly => return None,
+            Self::EmptyObject => problem.clone(),
+            Self::AboutBlank => problem.clone().with_about_blank(AboutBlank::FillTitle),
+        };
+
+        let body = problem.filled_json(true);
+        Some((problem, body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{assert, check};
+    use http::StatusCode;
+
+    use super::EmptyProblem;
+    use crate::StatusMember;
+
+    #[test]
+    fn status_only() {
+        check!(EmptyProblem::StatusOnly
+            .fill(&crate::new(StatusCode::NOT_FOUND))
+            .is_none());
+    }
+
+    #[test]
+    fn empty_object() {
+        let problem = crate::new(StatusCode::NOT_FOUND);
+
+        assert!(let Some((problem, body)) = EmptyProblem::EmptyObject.fill(&problem));
+        check!(problem.body.is_empty());
+        check!(body == "{}");
+    }
+
+    #[test]
+    fn empty_object_status_member() {
+        let problem = crate::new(StatusCode::NOT_FOUND).with_status_member(StatusMember::Insert);
+
+        assert!(let Some((problem, body)) = EmptyProblem::EmptyObject.fill(&problem));
+        check!(problem.body.is_empty());
+        check!(body == r#"{"status":404}"#);
+    }
+
+    #[test]
+    fn about_blank_status_member() {
+        let problem = crate::new(StatusCode::NOT_FOUND).with_status_member(StatusMember::Insert);
+
+        assert!(let Some((_, body)) = EmptyProblem::AboutBlank.fill(&problem));
+        check!(body == r#"{"status":404,"title":"Not Found"}"#);
+    }
+
+    #[test]
+    fn about_blank() {
+        let problem = crate::new(StatusCode::NOT_FOUND);
+
+        assert!(let Some((problem, body)) = EmptyProblem::AboutBlank.fill(&problem));
+        check!(problem.response_body()["title"] == "Not Found");
+        check!(body == r#"{"title":"Not Found"}"#);
+    }
+
+    #[test]
+    fn about_blank_without_reason() {
+        let problem = crate::new(StatusCode::from_u16(599).unwrap());
+
+        assert!(let Some((_, body)) = EmptyProblem::AboutBlank.fill(&problem));
+        check!(body == "{}");
+    }
+}
@@ -0,0 +1,283 @@
+//! Rendering of a `Problem` as an HTML page, for clients that prefer `text/html`.
+//!
+//! The default page shows the status, title, detail and instance of the problem, with a link to
+//! the documentation for the type, and a table of any extension members. Every value is escaped.
+//!
+//! The page can be replaced by registering a different [`HtmlRenderer`] with
+//! [`set_html_renderer`]. This is used for every problem that is rendered as HTML, including by
+//! the content negotiation for Axum and Poem.
+//!
+//! # Examples
+//! ```
+//! # use http::StatusCode;
+//! use problemdetails::{html, Format, Problem};
+//!
+//! html::set_html_renderer(|problem: &Problem| {
+//!     format!(
+//!         "<h1>Sorry, something went wrong</h1><p>{}</p>",
+//!         html::escape(problem.detail().unwrap_or_default())
+//!     )
+//! });
+//!
+//! let problem = problemdetails::new(StatusCode::NOT_FOUND).with_detail("No <user>");
+//! let (_, body) = problem.render(Format::Html);
+//! assert_eq!(
+//!     body,
+//!     "<h1>Sorry, something went wrong</h1><p>No &lt;user&gt;</p>"
+//! );
+//! # html::set_html_renderer(html::DefaultHtmlRenderer);
+//! ```
+
+use std::{fmt::Write, sync::RwLock};
+
+use serde_json::Value;
+
+use super::Problem;
+
+/// Members that the default page shows on their own, instead of in the table of extensions.
+const STANDARD_MEMBERS: [&str; 5] = ["type", "title", "detail", "instance", "status"];
+
+/// The renderer registered with [`set_html_renderer`], if any.
+static RENDERER: RwLock<Option<Box<dyn HtmlRenderer>>> = RwLock::new(None);
+
+/// Renders a `Problem` as a complete HTML page.
+pub trait HtmlRenderer: Send + Sync {
+    /// Render the problem as an HTML page.
+    ///
+    /// Implementations are responsible for escaping any values from the problem, for example with
+    /// [`escape`].
+    fn render(&self, problem: &Problem) -> String;
+}
+
+impl<F> HtmlRenderer for F
+where
+    F: Fn(&Problem) -> String + Send + Sync,
+{
+    fn render(&self, problem: &Problem) -> String {
+        self(problem)
+    }
+}
+
+/// The page that is used unless another renderer is registered with [`set_html_renderer`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultHtmlRenderer;
+
+impl HtmlRenderer for DefaultHtmlRenderer {
+    fn render(&self, problem: &Problem) -> String {
+        let status = escape(&problem.status_code.to_string());
+        let title = problem.title().or_else(|| problem.about_blank_title());
+        let heading = title.map_or_else(|| status.clone(), escape);
+
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        writeln!(html, "<title>{heading}</title>").expect("Writing to a String can't fail");
+        html.push_str("</head>\n<body>\n");
+        writeln!(html, "<h1>{heading}</h1>").expect("Writing to a String can't fail");
+
+        html.push_str("<dl>\n");
+        writeln!(html, "<dt>Status</dt>\n<dd>{status}</dd>")
+            .expect("Writing to a String can't fail");
+        if let Some(problem_type) = problem.problem_type() {
+            let problem_type = escape(problem_type);
+            if is_link(&problem_type) {
+                writeln!(
+                    html,
+                    "<dt>Type</dt>\n<dd><a href=\"{problem_type}\">{problem_type}</a></dd>"
+                )
+            } else {
+                writeln!(html, "<dt>Type</dt>\n<dd>{problem_type}</dd>")
+            }
+            .expect("Writing to a String can't fail");
+        }
+        if let Some(detail) = problem.detail() {
+            writeln!(html, "<dt>Detail</dt>\n<dd>{}</dd>", escape(detail))
+                .expect("Writing to a String can't fail");
+        }
+        if let Some(instance) = problem.instance() {
+            writeln!(html, "<dt>Instance</dt>\n<dd>{}</dd>", escape(instance))
+                .expect("Writing to a String can't fail");
+        }
+        html.push_str("</dl>\n");
+
+        let extensions: Vec<_> = problem
+            .body
+            .iter()
+            .filter(|(key, value)| !STANDARD_MEMBERS.contains(&key.as_str()) || !value.is_string())
+            .filter(|(key, _)| key.as_str() != "status")
+            .collect();
+        if !extensions.is_empty() {
+            html.push_str("<table>\n<tr><th>Member</th><th>Value</th></tr>\n");
+            for (key, value) in extensions {
+                let value = match value {
+                    Value::String(value) => escape(value),
+                    value => escape(&value.to_string()),
+                };
+                writeln!(html, "<tr><td>{}</td><td>{value}</td></tr>", escape(key))
+                    .expect("Writing to a String can't fail");
+            }
+            html.push_str("</table>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// Set the renderer to use for every problem that is rendered as HTML.
+///
+/// By default this is [`DefaultHtmlRenderer`].
+pub fn set_html_renderer<R>(renderer: R)
+where
+    R: HtmlRenderer + 'static,
+{
+    *RENDERER
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(Box::new(renderer));
+}
+
+/// Render a problem as HTML with the renderer registered by [`set_html_renderer`].
+pub(crate) fn render(problem: &Problem) -> String {
+    let renderer = RENDERER
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+    match renderer.as_deref() {
+        Some(renderer) => renderer.render(problem),
+        None => DefaultHtmlRenderer.render(problem),
+    }
+}
+
+/// Escape text for use in HTML, either as content or as the value of an attribute.
+#[must_use]
+pub fn escape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Check if a problem type can safely be used as a link.
+///
+/// Only absolute HTTP URIs are linked, so that something like a `javascript:` URI can never be
+/// followed from the page.
+fn is_link(problem_type: &str) -> bool {
+    let lower = problem_type.to_ascii_lowercase();
+
+    lower.starts_with("https://") || lower.starts_with("http://")
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+    use insta::assert_snapshot;
+    use serde_json::json;
+
+    use super::{DefaultHtmlRenderer, HtmlRenderer};
+
+    #[test]
+    fn full_page() {
+        let problem = crate::new(StatusCode::FORBIDDEN)
+            .with_type("https://example.com/probs/out-of-credit")
+            .with_title("You do not have enough credit.")
+            .with_detail("Your current balance is 30, but that costs 50.")
+            .with_instance("/account/12345/msgs/abc")
+            .with_value("balance", 30)
+            .with_value("accounts", vec!["/account/12345", "/account/67890"]);
+
+        assert_snapshot!(DefaultHtmlRenderer.render(&problem), @r###"
+        <!DOCTYPE html>
+        <html>
+        <head>
+        <meta charset="utf-8">
+        <title>You do not have enough credit.</title>
+        </head>
+        <body>
+        <h1>You do not have enough credit.</h1>
+        <dl>
+        <dt>Status</dt>
+        <dd>403 Forbidden</dd>
+        <dt>Type</dt>
+        <dd><a href="https://example.com/probs/out-of-credit">https://example.com/probs/out-of-credit</a></dd>
+        <dt>Detail</dt>
+        <dd>Your current balance is 30, but that costs 50.</dd>
+        <dt>Instance</dt>
+        <dd>/account/12345/msgs/abc</dd>
+        </dl>
+        <table>
+        <tr><th>Member</th><th>Value</th></tr>
+        <tr><td>accounts</td><td>[&quot;/account/12345&quot;,&quot;/account/67890&quot;]</td></tr>
+        <tr><td>balance</td><td>30</td></tr>
+        </table>
+        </body>
+        </html>
+        "###);
+    }
+
+    #[test]
+    fn empty_problem() {
+        let problem = crate::new(StatusCode::NOT_FOUND);
+
+        assert_snapshot!(DefaultHtmlRenderer.render(&problem), @r###"
+        <!DOCTYPE html>
+        <html>
+        <head>
+        <meta charset="utf-8">
+        <title>404 Not Found</title>
+        </head>
+        <body>
+        <h1>404 Not Found</h1>
+        <dl>
+        <dt>Status</dt>
+        <dd>404 Not Found</dd>
+        </dl>
+        </body>
+        </html>
+        "###);
+    }
+
+    #[test]
+    fn escaping() {
+        let problem = crate::new(StatusCode::BAD_REQUEST)
+            .with_type("javascript:alert('hi')")
+            .with_title("<script>alert(1)</script>")
+            .with_detail("Tom & \"Jerry\"")
+            .with_value("<b>", json!({"x": "<i>"}))
+            .with_value("instance", 42);
+
+        assert_snapshot!(DefaultHtmlRenderer.render(&problem), @r###"
+        <!DOCTYPE html>
+        <html>
+        <head>
+        <meta charset="utf-8">
+        <title>&lt;script&gt;alert(1)&lt;/script&gt;</title>
+        </head>
+        <body>
+        <h1>&lt;script&gt;alert(1)&lt;/script&gt;</h1>
+        <dl>
+        <dt>Status</dt>
+        <dd>400 Bad Request</dd>
+        <dt>Type</dt>
+        <dd>javascript:alert(&#39;hi&#39;)</dd>
+        <dt>Detail</dt>
+        <dd>Tom &amp; &quot;Jerry&quot;</dd>
+        </dl>
+        <table>
+        <tr><th>Member</th><th>Value</th></tr>
+        <tr><td>&lt;b&gt;</td><td>{&quot;x&quot;:&quot;&lt;i&gt;&quot;}</td></tr>
+        <tr><td>instance</td><td>42</td></tr>
+        </table>
+        </body>
+        </html>
+        "###);
+    }
+}
@@ -16,10 +16,39 @@
 //! When used with a supported HTTP Server, this will automatically generate the correct JSON
 //! response and set the Content-Type header to the correct value of `application/problem+json`.
 //!
-//! If used with an unsupported HTTP Server, the status code and body of the problem details can be
-//! extracted and sent manually. The `body` field is in the correct structure to format into JSON
-//! using something like `serde` already, so serializing it should be as simple as the HTTP Server
-//! allows for.
+//! If used with an unsupported HTTP Server, such as plain `hyper` or `lambda_http`,
+//! [`Problem::into_http_response`] builds an `http::Response` with the same status code, headers
+//! and body that the supported HTTP Servers send.
+//!
+//! The `status` member is not included in the response unless it is set manually. This can be
+//! changed with [`set_status_member`] for every problem, or with [`Problem::with_status_member`]
+//! for a single problem, so that the `status` member is always written from the status code.
+//! Similarly, [`set_about_blank`] and [`Problem::with_about_blank`] can fill in the `title` of
+//! problems without a `type` from the reason phrase of the status code, as RFC-9457 recommends for
+//! `about:blank` problems. Problems that still have an empty body are sent as just the status
+//! code, unless a different [`EmptyProblem`] policy is applied to the router.
+//!
+//! `Problem` implements `std::error::Error`, and its `Display` output is the status code, title
+//! and detail on one line. An underlying error can be attached with [`Problem::with_source`] so
+//! that it can be logged, but it is never sent to the client.
+//!
+//! `Problem` itself also implements `serde::Serialize` and `serde::Deserialize`, using the wire
+//! form from RFC-9457 with the status code written as the `status` member. This makes it possible
+//! to send problems between services, or store them, and read them back again.
+//!
+//! Problems can also be rendered as `application/problem+xml`, `text/plain` or `text/html`, with a
+//! [`Negotiator`] selecting the format from the `Accept` header of the request. For Axum this is
+//! done by adding `axum::NegotiateLayer` to the router, and for Poem by adding
+//! `poem::NegotiateMiddleware`. The HTML page can be replaced, as described in the [`html`] module.
+//! Axum's own error responses, such as rejections and unmatched routes, can be converted into
+//! problems with `axum::ConvertErrorsLayer`, and the rejections from its extractors are converted
+//! into problems with stable "type" URIs by `From`, or by the `axum::Json`, `axum::Query` and
+//! `axum::Path` extractors.
+//!
+//! The kinds of problem used by an API can be declared as `const` [`ProblemType`] values, which
+//! build problems with the right "type", "title" and status code, and can be checked for with
+//! [`Problem::is`]. They can also be described in a [`ProblemTypeRegistry`], which can serve
+//! documentation for each type.
 //!
 //! # Examples
 //! ## Create an empty problem.
@@ -38,24 +67,119 @@
 //!     .with_value("balance", 30)
 //!     .with_value("accounts", vec!["/account/12345", "/account/67890"]);
 //! ```
+//! ## Round-trip a problem through JSON.
+//! ```
+//! # use http::StatusCode;
+//! let problem = problemdetails::new(StatusCode::NOT_FOUND).with_title("Not Found");
+//!
+//! let json = serde_json::to_string(&problem).unwrap();
+//! assert_eq!(json, r#"{"status":404,"title":"Not Found"}"#);
+//!
+//! let parsed: problemdetails::Problem = serde_json::from_str(&json).unwrap();
+//! assert_eq!(parsed.status_code, StatusCode::NOT_FOUND);
+//! ```
 //! # Features
 //! HTTP Server support is behind feature flags for the appropriate HTTP Server. As such, you will
 //! need to enable the correct feature for the HTTP Server that you are using.
 //!
 //! Currently supported features are:
+//! * `actix` - For the [Actix Web](https://crates.io/crates/actix-web) HTTP Server.
 //! * `axum` - For the [Axum](https://crates.io/crates/axum) HTTP Server.
 //! * `poem` - For the [Poem](https://crates.io/crates/poem) HTTP Server.
+//! * `rocket` - For the [Rocket](https://crates.io/crates/rocket) HTTP Server.
+//! * `salvo` - For the [Salvo](https://crates.io/crates/salvo) HTTP Server.
+//! * `tide` - For the [Tide](https://crates.io/crates/tide) HTTP Server.
+//! * `warp` - For the [Warp](https://crates.io/crates/warp) HTTP Server.
+//!
+//! Support for consuming problems returned by other HTTP APIs is also behind a feature flag:
+//! * `client` - Adds `Problem::from_response`, for parsing an `application/problem+json` response.
+//! * `reqwest` - For the [Reqwest](https://crates.io/crates/reqwest) HTTP Client. This also enables
+//!   the `client` feature.
+//!
+//! Other features are:
+//! * `derive` - Adds `#[derive(IntoProblem)]`, for converting error types into a `Problem`.
+//! * `anyhow` - Adds `From<anyhow::Error> for Problem`. See the [`report`] module for details.
+//! * `eyre` - Adds `From<eyre::Report> for Problem`. See the [`report`] module for details.
+//! * `xml` - Adds `Problem::to_xml` and `Problem::from_xml`, for the `application/problem+xml`
+//!   format from RFC-9457 Appendix B.
 
+mod about_blank;
+#[cfg(feature = "actix")]
+pub mod actix;
 #[cfg(feature = "axum")]
 pub mod axum;
+#[cfg(feature = "client")]
+mod client;
+#[cfg(feature = "derive")]
+mod derive;
+mod detail;
+mod empty;
+pub mod html;
+mod negotiate;
+mod panic;
 #[cfg(feature = "poem")]
 pub mod poem;
+mod problem_type;
+mod registry;
+mod rejection;
+#[cfg(any(feature = "anyhow", feature = "eyre"))]
+pub mod report;
+#[cfg(feature = "reqwest")]
+pub mod reqwest;
+mod response;
+#[cfg(feature = "rocket")]
+pub mod rocket;
+#[cfg(feature = "salvo")]
+pub mod salvo;
+mod status;
+#[cfg(feature = "tide")]
+pub mod tide;
+pub mod types;
+mod validate;
+#[cfg(feature = "warp")]
+pub mod warp;
+#[cfg(feature = "xml")]
+mod xml;
 
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, error::Error, fmt, sync::Arc};
 
 use http::StatusCode;
+use serde::{
+    de::{self, Unexpected},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
 use serde_json::Value;
 
+#[cfg(feature = "client")]
+pub use crate::client::FromResponseError;
+#[cfg(feature = "derive")]
+pub use crate::derive::IntoProblem;
+#[cfg(feature = "xml")]
+pub use crate::xml::XmlError;
+pub use crate::{
+    about_blank::{set_about_blank, AboutBlank},
+    detail::set_fill_detail,
+    empty::EmptyProblem,
+    negotiate::{Format, Negotiator},
+    problem_type::ProblemType,
+    registry::{ProblemTypeRegistry, RegisteredType},
+    status::{set_status_member, StatusMember},
+    validate::MemberError,
+};
+
+// Allow the derive macros to refer to `::problemdetails` from inside this crate.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as problemdetails;
+
+/// Re-exports for use by the derive macros. Not public API.
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private {
+    pub use http::StatusCode;
+    pub use serde_json;
+}
+
 /// Representation of a Problem error to return to the client.
 #[allow(dead_code)] // These fields are used by the various features.
 #[derive(Debug, Clone)]
@@ -64,6 +188,12 @@ pub struct Problem {
     pub status_code: StatusCode,
     /// The actual body of the problem.
     pub body:        BTreeMap<String, Value>,
+    /// The policy for the `status` member, if this problem overrides the default.
+    status_member:   Option<StatusMember>,
+    /// The policy for `about:blank` problems, if this problem overrides the default.
+    about_blank:     Option<AboutBlank>,
+    /// The underlying error that caused the problem. This is never sent to the client.
+    source:          Option<Arc<dyn Error + Send + Sync>>,
 }
 
 /// Create a new `Problem` response to send to the client.
@@ -73,8 +203,11 @@ where
     S: Into<StatusCode>,
 {
     Problem {
-        status_code: status_code.into(),
-        body:        BTreeMap::new(),
+        status_code:   status_code.into(),
+        body:          BTreeMap::new(),
+        status_member: None,
+        about_blank:   None,
+        source:        None,
     }
 }
 
@@ -117,6 +250,9 @@ impl Problem {
 
     /// Specify an arbitrary value to include in the problem.
     ///
+    /// This does not check that the value is valid for the members defined by RFC-9457. Use
+    /// [`Problem::try_with_value`] for that.
+    ///
     /// # Parameters
     /// - `key` - The key for the value.
     /// - `value` - The value itself.
@@ -129,15 +265,517 @@ impl Problem {
 
         self
     }
+
+    /// Specify the underlying error that caused the problem.
+    ///
+    /// This is available through [`Error::source`], so that the cause can be logged, but is never
+    /// sent to the client.
+    #[must_use]
+    pub fn with_source<E>(mut self, source: E) -> Self
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        self.source = Some(Arc::new(source));
+
+        self
+    }
+
+    /// Get the "type" of the problem, if it is present and is a string.
+    #[must_use]
+    pub fn problem_type(&self) -> Option<&str> {
+        self.body.get("type").and_then(Value::as_str)
+    }
+
+    /// Get the "title" of the problem, if it is present and is a string.
+    #[must_use]
+    pub fn title(&self) -> Option<&str> {
+        self.body.get("title").and_then(Value::as_str)
+    }
+
+    /// Get the "detail" of the problem, if it is present and is a string.
+    #[must_use]
+    pub fn detail(&self) -> Option<&str> {
+        self.body.get("detail").and_then(Value::as_str)
+    }
+
+    /// Get the "instance" of the problem, if it is present and is a string.
+    #[must_use]
+    pub fn instance(&self) -> Option<&str> {
+        self.body.get("instance").and_then(Value::as_str)
+    }
+
+    /// Take the "type" out of the problem, if it is present and is a string.
+    pub fn take_type(&mut self) -> Option<String> {
+        self.take_string("type")
+    }
+
+    /// Take the "title" out of the problem, if it is present and is a string.
+    pub fn take_title(&mut self) -> Option<String> {
+        self.take_string("title")
+    }
+
+    /// Take the "detail" out of the problem, if it is present and is a string.
+    pub fn take_detail(&mut self) -> Option<String> {
+        self.take_string("detail")
+    }
+
+    /// Take the "instance" out of the problem, if it is present and is a string.
+    pub fn take_instance(&mut self) -> Option<String> {
+        self.take_string("instance")
+    }
+
+    /// Remove the "type" from the problem.
+    #[must_use]
+    pub fn remove_type(self) -> Self {
+        self.remove_value("type")
+    }
+
+    /// Remove the "title" from the problem.
+    #[must_use]
+    pub fn remove_title(self) -> Self {
+        self.remove_value("title")
+    }
+
+    /// Remove the "detail" from the problem.
+    #[must_use]
+    pub fn remove_detail(self) -> Self {
+        self.remove_value("detail")
+    }
+
+    /// Remove the "instance" from the problem.
+    #[must_use]
+    pub fn remove_instance(self) -> Self {
+        self.remove_value("instance")
+    }
+
+    /// Remove an arbitrary value from the problem.
+    ///
+    /// # Parameters
+    /// - `key` - The key for the value.
+    #[must_use]
+    pub fn remove_value(mut self, key: &str) -> Self {
+        self.body.remove(key);
+
+        self
+    }
+
+    /// Remove a value from the body if it is a string, and return it.
+    fn take_string(&mut self, key: &str) -> Option<String> {
+        match self.body.remove(key)? {
+            Value::String(value) => Some(value),
+            value => {
+                self.body.insert(key.to_owned(), value);
+                None
+            },
+        }
+    }
 }
 
-impl<S> From<S> for Problem
-where
-    S: Into<StatusCode>,
-{
-    fn from(status_code: S) -> Self {
-        new(status_code.into())
+impl From<StatusCode> for Problem {
+    fn from(status_code: StatusCode) -> Self {
+        new(status_code)
+    }
+}
+
+impl From<&StatusCode> for Problem {
+    fn from(status_code: &StatusCode) -> Self {
+        new(*status_code)
+    }
+}
+
+/// Renders the status code, title and detail of the problem on one line.
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.status_code)?;
+
+        match (self.title(), self.detail()) {
+            (Some(title), Some(detail)) => write!(f, ": {title} ({detail})"),
+            (Some(message), None) | (None, Some(message)) => write!(f, ": {message}"),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+impl Error for Problem {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn Error + 'static))
     }
 }
+
+/// Name of the member that carries the status code in the serialized form of a problem.
+const STATUS: &str = "status";
+
+/// Title of the problems sent for internal errors, such as panics and unhandled reports.
+const INTERNAL_SERVER_ERROR_TITLE: &str = "Internal server error";
+
+/// Serializes the problem in the RFC-9457 wire form.
+///
+/// The `status_code` is written as the `status` member, replacing any `status` value that is
+/// present in the body.
+impl Serialize for Problem {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let members = self.body.iter().filter(|(key, _)| key.as_str() != STATUS);
+        let mut status = Some(self.status_code.as_u16());
+
+        let mut map = serializer.serialize_map(Some(members.clone().count() + 1))?;
+        for (key, value) in members {
+            if key.as_str() > STATUS {
+                if let Some(status) = status.take() {
+                    map.serialize_entry(STATUS, &status)?;
+                }
+            }
+            map.serialize_entry(key, value)?;
+        }
+        if let Some(status) = status {
+            map.serialize_entry(STATUS, &status)?;
+        }
+        map.end()
+    }
+}
+
+/// Deserializes the problem from the RFC-9457 wire form.
+///
+/// The `status` member is required, and is used as the `status_code` of the problem instead of
+/// being kept in the body.
+impl<'de> Deserialize<'de> for Problem {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut body = BTreeMap::<String, Value>::deserialize(deserializer)?;
+
+        let status = body
+            .remove(STATUS)
+            .ok_or_else(|| de::Error::missing_field(STATUS))?;
+        let status_code = status
+            .as_u64()
+            .ok_or_else(|| de::Error::invalid_type(unexpected(&status), &ExpectedStatus))?;
+        let status_code = u16::try_from(status_code)
+            .ok()
+            .and_then(|status_code| StatusCode::from_u16(status_code).ok())
+            .ok_or_else(|| de::Error::invalid_value(unexpected(&status), &ExpectedStatus))?;
+
+        Ok(Self {
+            body,
+            ..new(status_code)
+        })
+    }
+}
+
+/// Description of the expected `status` member, used in deserialization errors.
+struct ExpectedStatus;
+
+impl de::Expected for ExpectedStatus {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an HTTP status code between 100 and 999")
+    }
+}
+
+/// Describe a JSON value for use in deserialization errors.
+fn unexpected(value: &Value) -> Unexpected<'_> {
+    match value {
+        Value::Null => Unexpected::Unit,
+        Value::Bool(b) => Unexpected::Bool(*b),
+        Value::Number(n) => n
+            .as_u64()
+            .map(Unexpected::Unsigned)
+            .or_else(|| n.as_i64().map(Unexpected::Signed))
+            .or_else(|| n.as_f64().map(Unexpected::Float))
+            .unwrap_or(Unexpected::Other("number")),
+        Value::String(s) => Unexpected::Str(s),
+        Value::Array(_) => Unexpected::Seq,
+        Value::Object(_) => Unexpected::Map,
+    }
+}
+
 /// Result type where the error is always a `Problem`.
 pub type Result<T> = std::result::Result<T, Problem>;
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use assert2::check;
+    use http::StatusCode;
+    use insta::assert_json_snapshot;
+    use serde_json::json;
+
+    use crate::Problem;
+
+    fn forbidden_example() -> Problem {
+        crate::new(StatusCode::FORBIDDEN)
+            .with_type("https://example.com/probs/out-of-credit")
+            .with_title("You do not have enough credit.")
+            .with_detail("Your current balance is 30, but that costs 50.")
+            .with_instance("/account/12345/msgs/abc")
+    }
+
+    #[test]
+    fn get_standard_members() {
+        let problem = forbidden_example();
+
+        check!(problem.problem_type() == Some("https://example.com/probs/out-of-credit"));
+        check!(problem.title() == Some("You do not have enough credit."));
+        check!(problem.detail() == Some("Your current balance is 30, but that costs 50."));
+        check!(problem.instance() == Some("/account/12345/msgs/abc"));
+    }
+
+    #[test]
+    fn get_missing_members() {
+        let problem = crate::new(StatusCode::BAD_REQUEST);
+
+        check!(problem.problem_type() == None);
+        check!(problem.title() == None);
+        check!(problem.detail() == None);
+        check!(problem.instance() == None);
+    }
+
+    #[test]
+    fn get_non_string_member() {
+        let problem = crate::new(StatusCode::BAD_REQUEST).with_value("title", 42);
+
+        check!(problem.title() == None);
+    }
+
+    #[test]
+    fn take_standard_members() {
+        let mut problem = forbidden_example();
+
+        check!(problem.take_type().as_deref() == Some("https://example.com/probs/out-of-credit"));
+        check!(problem.take_title().as_deref() == Some("You do not have enough credit."));
+        check!(
+            problem.take_detail().as_deref()
+                == Some("Your current balance is 30, but that costs 50.")
+        );
+        check!(problem.take_instance().as_deref() == Some("/account/12345/msgs/abc"));
+        check!(problem.body.is_empty());
+
+        check!(problem.take_title() == None);
+    }
+
+    #[test]
+    fn take_non_string_member() {
+        let mut problem = crate::new(StatusCode::BAD_REQUEST).with_value("title", 42);
+
+        check!(problem.take_title() == None);
+        check!(problem.body.get("title") == Some(&json!(42)));
+    }
+
+    #[test]
+    fn remove_standard_members() {
+        let problem = forbidden_example()
+            .with_value("balance", 30)
+            .remove_type()
+            .remove_title()
+            .remove_detail()
+            .remove_instance();
+
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "balance": 30
+        }
+        "###);
+    }
+
+    #[test]
+    fn fill_missing_instance() {
+        let mut problem = crate::new(StatusCode::NOT_FOUND);
+        if problem.instance().is_none() {
+            problem = problem.with_instance("/requests/1");
+        }
+
+        check!(problem.instance() == Some("/requests/1"));
+    }
+
+    #[test]
+    fn display_title_and_detail() {
+        let problem = forbidden_example();
+
+        check!(
+            problem.to_string()
+                == "403 Forbidden: You do not have enough credit. (Your current balance is 30, but \
+                    that costs 50.)"
+        );
+    }
+
+    #[test]
+    fn display_title_only() {
+        let problem = crate::new(StatusCode::NOT_FOUND).with_title("Not Found");
+
+        check!(problem.to_string() == "404 Not Found: Not Found");
+    }
+
+    #[test]
+    fn display_detail_only() {
+        let problem = crate::new(StatusCode::NOT_FOUND).with_detail("No user with ID 1");
+
+        check!(problem.to_string() == "404 Not Found: No user with ID 1");
+    }
+
+    #[test]
+    fn display_no_values() {
+        let problem = crate::new(StatusCode::BAD_REQUEST);
+
+        check!(problem.to_string() == "400 Bad Request");
+    }
+
+    #[test]
+    fn source() {
+        let cause = std::io::Error::new(std::io::ErrorKind::NotFound, "file missing");
+        let problem = crate::new(StatusCode::NOT_FOUND).with_source(cause);
+
+        let source = problem.source();
+        check!(source.map(ToString::to_string).as_deref() == Some("file missing"));
+        check!(problem.clone().source().is_some());
+
+        assert_json_snapshot!(problem, @r###"
+        {
+          "status": 404
+        }
+        "###);
+    }
+
+    #[test]
+    fn no_source() {
+        let problem = crate::new(StatusCode::NOT_FOUND);
+
+        check!(problem.source().is_none());
+    }
+
+    #[test]
+    fn into_boxed_error() {
+        fn fails() -> std::result::Result<(), Box<dyn Error + Send + Sync>> {
+            Err(crate::new(StatusCode::NOT_FOUND).with_title("Not Found"))?;
+            Ok(())
+        }
+
+        let err = fails().unwrap_err();
+        check!(err.to_string() == "404 Not Found: Not Found");
+    }
+
+    #[test]
+    fn serialize_no_values() {
+        let problem = crate::new(StatusCode::BAD_REQUEST);
+
+        assert_json_snapshot!(problem, @r###"
+        {
+          "status": 400
+        }
+        "###);
+    }
+
+    #[test]
+    fn serialize_rfc7807_forbidden_example() {
+        let problem = crate::new(StatusCode::FORBIDDEN)
+            .with_type("https://example.com/probs/out-of-credit")
+            .with_title("You do not have enough credit.")
+            .with_detail("Your current balance is 30, but that costs 50.")
+            .with_instance("/account/12345/msgs/abc")
+            .with_value("balance", 30)
+            .with_value("accounts", vec!["/account/12345", "/account/67890"]);
+
+        assert_json_snapshot!(problem, @r###"
+        {
+          "accounts": [
+            "/account/12345",
+            "/account/67890"
+          ],
+          "balance": 30,
+          "detail": "Your current balance is 30, but that costs 50.",
+          "instance": "/account/12345/msgs/abc",
+          "status": 403,
+          "title": "You do not have enough credit.",
+          "type": "https://example.com/probs/out-of-credit"
+        }
+        "###);
+    }
+
+    #[test]
+    fn serialize_replaces_status_value() {
+        let problem = crate::new(StatusCode::NOT_FOUND).with_value("status", "oops");
+
+        assert_json_snapshot!(problem, @r###"
+        {
+          "status": 404
+        }
+        "###);
+    }
+
+    #[test]
+    fn deserialize_rfc7807_forbidden_example() {
+        let problem: Problem = serde_json::from_value(json!({
+            "type": "https://example.com/probs/out-of-credit",
+            "title": "You do not have enough credit.",
+            "status": 403,
+            "detail": "Your current balance is 30, but that costs 50.",
+            "instance": "/account/12345/msgs/abc",
+            "balance": 30,
+            "accounts": ["/account/12345", "/account/67890"]
+        }))
+        .unwrap();
+
+        check!(problem.status_code == StatusCode::FORBIDDEN);
+        check!(problem.body.get("status") == None);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "accounts": [
+            "/account/12345",
+            "/account/67890"
+          ],
+          "balance": 30,
+          "detail": "Your current balance is 30, but that costs 50.",
+          "instance": "/account/12345/msgs/abc",
+          "title": "You do not have enough credit.",
+          "type": "https://example.com/probs/out-of-credit"
+        }
+        "###);
+    }
+
+    #[test]
+    fn round_trip() {
+        let problem = crate::new(StatusCode::IM_A_TEAPOT)
+            .with_title("I'm a teapot")
+            .with_value("nested", json!({"a": [1, 2, 3]}));
+
+        let json = serde_json::to_string(&problem).unwrap();
+        let parsed: Problem = serde_json::from_str(&json).unwrap();
+
+        check!(parsed.status_code == problem.status_code);
+        check!(parsed.body == problem.body);
+    }
+
+    #[test]
+    fn deserialize_missing_status() {
+        let err = serde_json::from_value::<Problem>(json!({ "title": "Missing" })).unwrap_err();
+        check!(err.to_string() == "missing field `status`");
+    }
+
+    #[test]
+    fn deserialize_non_numeric_status() {
+        let err = serde_json::from_value::<Problem>(json!({ "status": "404" })).unwrap_err();
+        check!(
+            err.to_string()
+                == "invalid type: string \"404\", expected an HTTP status code between 100 and 999"
+        );
+    }
+
+    #[test]
+    fn deserialize_out_of_range_status() {
+        let err = serde_json::from_value::<Problem>(json!({ "status": 1000 })).unwrap_err();
+        check!(
+            err.to_string()
+                == "invalid value: integer `1000`, expected an HTTP status code between 100 and 999"
+        );
+    }
+
+    #[test]
+    fn deserialize_not_an_object() {
+        let err = serde_json::from_value::<Problem>(json!([404])).unwrap_err();
+        check!(err.to_string() == "invalid type: sequence, expected a map");
+    }
+}
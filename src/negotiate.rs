@@ -0,0 +1,390 @@
+use std::fmt::Write;
+
+use http::HeaderValue;
+use serde_json::Value;
+
+use super::Problem;
+
+/// The formats that a `Problem` can be rendered in.
+///
+/// Some formats are only available with a feature, such as `ProblemXml` with the `xml` feature, so
+/// matches on this need a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Format {
+    /// `application/problem+json`, as defined by RFC-9457.
+    ProblemJson,
+    /// `application/problem+xml`, as defined by RFC-9457 Appendix B.
+    #[cfg(feature = "xml")]
+    ProblemXml,
+    /// `application/json`, for clients that reject `application/problem+json`. The body is the
+    /// same as for [`Format::ProblemJson`].
+    Json,
+    /// `text/plain`, with one line for the status code and one for each member.
+    Text,
+    /// `text/html`, as a page describing the problem. See the [`html`](crate::html) module.
+    Html,
+}
+
+impl Format {
+    /// Every format, in order of preference when the client accepts more than one equally.
+    const ALL: &'static [Self] = &[
+        Self::ProblemJson,
+        #[cfg(feature = "xml")]
+        Self::ProblemXml,
+        Self::Json,
+        Self::Html,
+        Self::Text,
+    ];
+
+    /// Get the value of the `Content-Type` header for this format.
+    #[must_use]
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::ProblemJson => "application/problem+json",
+            #[cfg(feature = "xml")]
+            Self::ProblemXml => "application/problem+xml",
+            Self::Json => "application/json",
+            Self::Text => "text/plain; charset=utf-8",
+            Self::Html => "text/html; charset=utf-8",
+        }
+    }
+
+    /// Get the type and subtype of the media type for this format.
+    fn media_type(self) -> (&'static str, &'static str) {
+        match self {
+            Self::ProblemJson => ("application", "problem+json"),
+            #[cfg(feature = "xml")]
+            Self::ProblemXml => ("application", "problem+xml"),
+            Self::Json => ("application", "json"),
+            Self::Text => ("text", "plain"),
+            Self::Html => ("text", "html"),
+        }
+    }
+}
+
+/// Selects the format to render a `Problem` in, based on the `Accept` header of the request.
+///
+/// Quality values in the header are respected, and the most specific media range that matches a
+/// format decides its quality. When several formats are equally acceptable,
+/// `application/problem+json` is preferred, followed by `application/problem+xml`, `text/html` and
+/// then `text/plain`.
+///
+/// # Examples
+/// ```
+/// # use http::HeaderValue;
+/// use problemdetails::{Format, Negotiator};
+///
+/// let negotiator = Negotiator::new().with_fallback(Format::Text);
+///
+/// let accept = HeaderValue::from_static("text/html;q=0.8, text/plain;q=0.9");
+/// assert_eq!(negotiator.negotiate(Some(&accept)), Format::Text);
+///
+/// let accept = HeaderValue::from_static("image/png");
+/// assert_eq!(negotiator.negotiate(Some(&accept)), Format::Text);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Negotiator {
+    fallback:           Format,
+    json_compatibility: bool,
+}
+
+impl Default for Negotiator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Negotiator {
+    /// Create a negotiator that falls back to `application/problem+json`, without the
+    /// `application/json` compatibility mode.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            fallback:           Format::ProblemJson,
+            json_compatibility: false,
+        }
+    }
+
+    /// Set the format to use when there is no `Accept` header, or nothing in it matches a format.
+    #[must_use]
+    pub fn with_fallback(mut self, fallback: Format) -> Self {
+        self.fallback = fallback;
+
+        self
+    }
+
+    /// Enable answering clients that accept `application/json` with that content type instead of
+    /// `application/problem+json`, for clients that reject the latter.
+    ///
+    /// By default this is `false`.
+    #[must_use]
+    pub fn with_json_compatibility(mut self, enabled: bool) -> Self {
+        self.json_compatibility = enabled;
+
+        self
+    }
+
+    /// Select the format to use for a request with the given `Accept` header.
+    #[must_use]
+    pub fn negotiate(&self, accept: Option<&HeaderValue>) -> Format {
+        let Some(accept) = accept.and_then(|accept| accept.to_str().ok()) else {
+            return self.fallback;
+        };
+        let ranges: Vec<MediaRange> = accept.split(',').filter_map(MediaRange::parse).collect();
+
+        Format::ALL
+            .iter()
+            .enumerate()
+            .filter(|(_, format)| self.json_compatibility || **format != Format::Json)
+            .filter_map(|(index, format)| {
+                let (quality, specificity) = ranges
+                    .iter()
+                    .filter_map(|range| range.matches(*format).map(|s| (range.quality, s)))
+                    .max_by_key(|(_, specificity)| *specificity)?;
+
+                (quality > 0).then_some(((quality, specificity, std::cmp::Reverse(index)), *format))
+            })
+            .max_by_key(|(rank, _)| *rank)
+            .map_or(self.fallback, |(_, format)| format)
+    }
+}
+
+/// A single media range from an `Accept` header.
+struct MediaRange<'a> {
+    media_type: &'a str,
+    subtype:    &'a str,
+    /// The quality of the range, in thousandths.
+    quality:    u16,
+}
+
+impl<'a> MediaRange<'a> {
+    /// Parse a media range, ignoring it if it is not valid.
+    fn parse(range: &'a str) -> Option<Self> {
+        let mut parts = range.split(';');
+        let (media_type, subtype) = parts.next()?.trim().split_once('/')?;
+
+        let mut quality = 1000;
+        for param in parts {
+            if let Some((name, value)) = param.split_once('=') {
+                if name.trim().eq_ignore_ascii_case("q") {
+                    quality = parse_quality(value.trim())?;
+                }
+            }
+        }
+
+        Some(Self {
+            media_type: media_type.trim(),
+            subtype: subtype.trim(),
+            quality,
+        })
+    }
+
+    /// Check if the range matches a format, and if so how specifically.
+    fn matches(&self, format: Format) -> Option<u8> {
+        let (media_type, subtype) = format.media_type();
+
+        if self.media_type == "*" && self.subtype == "*" {
+            Some(0)
+        } else if !self.media_type.eq_ignore_ascii_case(media_type) {
+            None
+        } else if self.subtype == "*" {
+            Some(1)
+        } else if self.subtype.eq_ignore_ascii_case(subtype) {
+            Some(2)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parse a quality value into thousandths.
+fn parse_quality(value: &str) -> Option<u16> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if fraction.len() > 3 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let whole = match whole {
+        "0" => 0,
+        "1" => 1000,
+        _ => return None,
+    };
+    let quality = whole + format!("{fraction:0<3}").parse::<u16>().ok()?;
+
+    (quality <= 1000).then_some(quality)
+}
+
+impl Problem {
+    /// Render the body of the problem in the given format.
+    ///
+    /// The returned format is the one that was actually used, which is `application/problem+json`
+    /// if the problem can not be written as XML.
+    #[must_use]
+    pub fn render(&self, format: Format) -> (Format, String) {
+        let body = match format {
+            #[cfg(feature = "xml")]
+            Format::ProblemXml => match self.response_xml() {
+                Ok(body) => body,
+                Err(_) => return self.render(Format::ProblemJson),
+            },
+            Format::ProblemJson | Format::Json => self.response_json(),
+            Format::Text => self.render_text(),
+            Format::Html => crate::html::render(self),
+        };
+
+        (format, body)
+    }
+
+    /// Render the problem as plain text.
+    fn render_text(&self) -> String {
+        let mut text = format!("{}\n", self.status_code);
+
+        // The status code is already on the first line.
+        let body = self.response_body();
+        for (key, value) in body.iter().filter(|(key, _)| key.as_str() != "status") {
+            match value {
+                Value::String(value) => writeln!(text, "{key}: {value}"),
+                value => writeln!(text, "{key}: {value}"),
+            }
+            .expect("Writing to a String can't fail");
+        }
+
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use http::{HeaderValue, StatusCode};
+    use insta::assert_snapshot;
+
+    use super::{Format, Negotiator};
+
+    fn negotiate(negotiator: Negotiator, accept: &'static str) -> Format {
+        negotiator.negotiate(Some(&HeaderValue::from_static(accept)))
+    }
+
+    #[test]
+    fn missing_accept_uses_fallback() {
+        check!(Negotiator::new().negotiate(None) == Format::ProblemJson);
+        check!(
+            Negotiator::new()
+                .with_fallback(Format::Html)
+                .negotiate(None)
+                == Format::Html
+        );
+    }
+
+    #[test]
+    fn any() {
+        check!(negotiate(Negotiator::new(), "*/*") == Format::ProblemJson);
+    }
+
+    #[test]
+    fn exact() {
+        check!(negotiate(Negotiator::new(), "text/plain") == Format::Text);
+        check!(negotiate(Negotiator::new(), "TEXT/HTML") == Format::Html);
+    }
+
+    #[test]
+    fn browser() {
+        let accept = "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8";
+
+        check!(negotiate(Negotiator::new(), accept) == Format::Html);
+    }
+
+    #[test]
+    fn quality_values() {
+        let accept = "application/problem+json;q=0.4, text/plain;q=0.5, text/html;q=0";
+
+        check!(negotiate(Negotiator::new(), accept) == Format::Text);
+    }
+
+    #[test]
+    fn most_specific_range_wins() {
+        check!(negotiate(Negotiator::new(), "text/*;q=0.9, text/plain;q=0.1") == Format::Html);
+        check!(negotiate(Negotiator::new(), "*/*;q=0.1, text/plain") == Format::Text);
+    }
+
+    #[test]
+    fn nothing_matches() {
+        let negotiator = Negotiator::new().with_fallback(Format::Text);
+
+        check!(negotiate(negotiator, "image/png") == Format::Text);
+        check!(negotiate(negotiator, "application/problem+json;q=0") == Format::Text);
+        check!(negotiate(negotiator, "nonsense") == Format::Text);
+    }
+
+    #[test]
+    fn invalid_quality_is_ignored() {
+        check!(negotiate(Negotiator::new(), "text/html;q=2, text/plain;q=0.5") == Format::Text);
+    }
+
+    #[test]
+    fn json_compatibility() {
+        check!(negotiate(Negotiator::new(), "application/json") == Format::ProblemJson);
+
+        let negotiator = Negotiator::new().with_json_compatibility(true);
+        check!(negotiate(negotiator, "application/json") == Format::Json);
+        check!(negotiate(negotiator, "application/json, */*") == Format::Json);
+        check!(negotiate(negotiator, "*/*") == Format::ProblemJson);
+    }
+
+    #[cfg(feature = "xml")]
+    #[test]
+    fn xml() {
+        check!(negotiate(Negotiator::new(), "application/problem+xml") == Format::ProblemXml);
+    }
+
+    fn forbidden_example() -> crate::Problem {
+        crate::new(StatusCode::FORBIDDEN)
+            .with_type("https://example.com/probs/out-of-credit")
+            .with_title("You do not have enough credit.")
+            .with_detail("Your current balance is 30, but that costs <50>.")
+            .with_value("balance", 30)
+    }
+
+    #[test]
+    fn render_text() {
+        let (format, body) = forbidden_example().render(Format::Text);
+
+        check!(format == Format::Text);
+        assert_snapshot!(body, @r###"
+        403 Forbidden
+        balance: 30
+        detail: Your current balance is 30, but that costs <50>.
+        title: You do not have enough credit.
+        type: https://example.com/probs/out-of-credit
+        "###);
+    }
+
+    #[cfg(feature = "xml")]
+    #[test]
+    fn render_invalid_xml() {
+        let (format, body) = crate::new(StatusCode::BAD_REQUEST)
+            .with_value("not valid", 1)
+            .render(Format::ProblemXml);
+
+        check!(format == Format::ProblemJson);
+        check!(body == r#"{"not valid":1}"#);
+    }
+
+    #[cfg(feature = "xml")]
+    #[test]
+    fn render_xml_about_blank() {
+        let (format, body) = crate::new(StatusCode::NOT_FOUND)
+            .with_about_blank(crate::AboutBlank::FillTitle)
+            .render(Format::ProblemXml);
+
+        check!(format == Format::ProblemXml);
+        assert_snapshot!(body, @r###"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <problem xmlns="urn:ietf:rfc:7807">
+          <status>404</status>
+          <title>Not Found</title>
+        </problem>
+        "###);
+    }
+}
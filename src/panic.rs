@@ -0,0 +1,74 @@
+use std::any::Any;
+
+use super::Problem;
+
+/// Get the message that a panic was raised with, if it had one.
+fn panic_message(err: &(dyn Any + Send)) -> &str {
+    if let Some(message) = err.downcast_ref::<String>() {
+        message
+    } else if let Some(message) = err.downcast_ref::<&str>() {
+        message
+    } else {
+        crate::INTERNAL_SERVER_ERROR_TITLE
+    }
+}
+
+/// Build the problem to send in response to a panic, from the base problem of a panic handler.
+///
+/// If `fill_detail` is enabled then the `detail` is replaced with the panic message.
+#[allow(dead_code)] // This is used by the server features.
+pub(crate) fn panic_problem(
+    problem: &Problem,
+    fill_detail: bool,
+    err: &(dyn Any + Send),
+) -> Problem {
+    if fill_detail {
+        problem.clone().with_detail(panic_message(err))
+    } else {
+        problem.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use http::StatusCode;
+
+    use super::panic_problem;
+
+    #[test]
+    fn string_message() {
+        let err: Box<dyn std::any::Any + Send> = Box::new(String::from("Oh no!"));
+        let problem = panic_problem(&crate::new(StatusCode::INTERNAL_SERVER_ERROR), true, &*err);
+
+        check!(problem.detail() == Some("Oh no!"));
+    }
+
+    #[test]
+    fn str_message() {
+        let err: Box<dyn std::any::Any + Send> = Box::new("Oh no!");
+        let problem = panic_problem(&crate::new(StatusCode::INTERNAL_SERVER_ERROR), true, &*err);
+
+        check!(problem.detail() == Some("Oh no!"));
+    }
+
+    #[test]
+    fn other_payload() {
+        let err: Box<dyn std::any::Any + Send> = Box::new(42);
+        let problem = panic_problem(&crate::new(StatusCode::INTERNAL_SERVER_ERROR), true, &*err);
+
+        check!(problem.detail() == Some("Internal server error"));
+    }
+
+    #[test]
+    fn without_detail() {
+        let err: Box<dyn std::any::Any + Send> = Box::new("Oh no!");
+        let problem = panic_problem(
+            &crate::new(StatusCode::INTERNAL_SERVER_ERROR).with_detail("Something broke"),
+            false,
+            &*err,
+        );
+
+        check!(problem.detail() == Some("Something broke"));
+    }
+}
@@ -1,39 +1,347 @@
-use http::{header::CONTENT_TYPE, HeaderMap};
-
-use super::Problem;
-
-impl poem::IntoResponse for Problem {
-    fn into_response(self) -> poem::Response {
-        if self.body.is_empty() {
-            self.status_code.into_response()
-        } else {
-            let body = poem::web::Json(self.body.clone());
-
-            let mut headers = HeaderMap::new();
-            headers.insert(
-                CONTENT_TYPE,
-                "application/problem+json"
-                    .parse()
-                    .expect("Could not parse the content type for problem detail"),
-            );
-
-            (self.status_code, headers, body).into_response()
+use std::any::Any;
+
+use http::{
+    header::{ACCEPT, CONTENT_LENGTH, CONTENT_TYPE},
+    HeaderValue, StatusCode,
+};
+use poem::{
+    endpoint::make_sync,
+    error::ResponseError,
+    get,
+    middleware::{CatchPanic, PanicHandler},
+    web::Html,
+    Endpoint, IntoResponse, Middleware, Request, Response, ResponseParts, Route,
+};
+
+use super::{EmptyProblem, Format, Negotiator, Problem, ProblemTypeRegistry};
+
+/// The `Problem` is kept in the extensions of the response, so that middleware such as
+/// [`NegotiateMiddleware`] can render it again.
+impl IntoResponse for Problem {
+    fn into_response(self) -> Response {
+        let (parts, body) = self.into_http_response().into_parts();
+
+        Response::from_parts(
+            ResponseParts {
+                status:     parts.status,
+                version:    parts.version,
+                headers:    parts.headers,
+                extensions: parts.extensions,
+            },
+            body.into(),
+        )
+    }
+}
+
+/// Allows a `Problem` to be returned as a `poem::Error`, for example with `?` from a handler that
+/// returns `poem::Result`.
+impl ResponseError for Problem {
+    fn status(&self) -> StatusCode {
+        self.status_code
+    }
+
+    fn as_response(&self) -> Response {
+        self.clone().into_response()
+    }
+}
+
+/// Create a route that serves the documentation page for every type in the registry, at the path
+/// of its URI. This can be nested into the route for the API.
+///
+/// Types whose URI is not an HTTP URI or a path, such as `tag:` or `urn:` URIs, are not served.
+///
+/// # Examples
+/// ```
+/// # use http::StatusCode;
+/// use poem::Route;
+/// use problemdetails::ProblemTypeRegistry;
+///
+/// let registry = ProblemTypeRegistry::new().with_type(
+///     "/probs/out-of-credit",
+///     StatusCode::FORBIDDEN,
+///     "You do not have enough credit.",
+///     "The account does not have enough credit to pay for the request.",
+/// );
+///
+/// let app = Route::new().nest("/", problemdetails::poem::documentation_route(&registry));
+/// ```
+#[must_use]
+pub fn documentation_route(registry: &ProblemTypeRegistry) -> Route {
+    registry
+        .documentation_pages()
+        .into_iter()
+        .fold(Route::new(), |route, (path, page)| {
+            route.at(path, get(make_sync(move |_| Html(page.clone()))))
+        })
+}
+
+/// Middleware that renders problem responses in the format selected by the `Accept` header of the
+/// request, using a [`Negotiator`].
+///
+/// This applies to every response that was produced from a `Problem`, including errors returned
+/// by the endpoint. Problems with an empty body are left as they are.
+///
+/// # Examples
+/// ```
+/// use poem::{EndpointExt, Route};
+/// use problemdetails::{poem::NegotiateMiddleware, Format, Negotiator};
+///
+/// let app = Route::new().with(NegotiateMiddleware::new(
+///     Negotiator::new().with_fallback(Format::Html),
+/// ));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NegotiateMiddleware {
+    negotiator: Negotiator,
+}
+
+impl NegotiateMiddleware {
+    /// Create a middleware that uses the given negotiator.
+    #[must_use]
+    pub fn new(negotiator: Negotiator) -> Self {
+        Self { negotiator }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for NegotiateMiddleware {
+    type Output = NegotiateEndpoint<E>;
+
+    fn transform(&self, inner: E) -> Self::Output {
+        NegotiateEndpoint {
+            inner,
+            negotiator: self.negotiator,
+        }
+    }
+}
+
+/// Endpoint that renders problem responses in the format selected by the `Accept` header of the
+/// request. Created by [`NegotiateMiddleware`].
+#[derive(Debug, Clone, Copy)]
+pub struct NegotiateEndpoint<E> {
+    inner:      E,
+    negotiator: Negotiator,
+}
+
+impl<E: Endpoint> Endpoint for NegotiateEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, request: Request) -> poem::Result<Self::Output> {
+        let format = self.negotiator.negotiate(request.headers().get(ACCEPT));
+        let response = call_endpoint(&self.inner, request).await?;
+
+        Ok(render_response(response, format))
+    }
+}
+
+/// Middleware that applies an [`EmptyProblem`] policy to every response that was produced from a
+/// `Problem` with an empty body, including errors returned by the endpoint.
+///
+/// If this is used along with [`NegotiateMiddleware`], it should be added before it so that
+/// `about:blank` problems are then rendered in the negotiated format.
+///
+/// # Examples
+/// ```
+/// use poem::{EndpointExt, Route};
+/// use problemdetails::{
+///     poem::{EmptyProblemMiddleware, NegotiateMiddleware},
+///     EmptyProblem,
+/// };
+///
+/// let app = Route::new()
+///     .with(EmptyProblemMiddleware::new(EmptyProblem::AboutBlank))
+///     .with(NegotiateMiddleware::default());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmptyProblemMiddleware {
+    policy: EmptyProblem,
+}
+
+impl EmptyProblemMiddleware {
+    /// Create a middleware that uses the given policy.
+    #[must_use]
+    pub fn new(policy: EmptyProblem) -> Self {
+        Self { policy }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for EmptyProblemMiddleware {
+    type Output = EmptyProblemEndpoint<E>;
+
+    fn transform(&self, inner: E) -> Self::Output {
+        EmptyProblemEndpoint {
+            inner,
+            policy: self.policy,
+        }
+    }
+}
+
+/// Endpoint that applies an [`EmptyProblem`] policy to problem responses. Created by
+/// [`EmptyProblemMiddleware`].
+#[derive(Debug, Clone, Copy)]
+pub struct EmptyProblemEndpoint<E> {
+    inner:  E,
+    policy: EmptyProblem,
+}
+
+impl<E: Endpoint> Endpoint for EmptyProblemEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, request: Request) -> poem::Result<Self::Output> {
+        let response = call_endpoint(&self.inner, request).await?;
+
+        Ok(fill_empty_problem(response, self.policy))
+    }
+}
+
+/// Call an endpoint and convert the result into a response, keeping any `Problem` in the
+/// extensions of the response.
+///
+/// `poem::Error` replaces the extensions of the response it creates, so a `Problem` that was
+/// returned as an error has to be turned back into a response here. Any other error is returned
+/// as it is, so that it can still be handled by outer middleware.
+async fn call_endpoint<E: Endpoint>(endpoint: &E, request: Request) -> poem::Result<Response> {
+    match endpoint.call(request).await {
+        Ok(output) => Ok(output.into_response()),
+        Err(err) => match err.downcast_ref::<Problem>() {
+            Some(problem) => Ok(problem.clone().into_response()),
+            None => Err(err),
+        },
+    }
+}
+
+/// Apply the policy to the response if it was produced from a `Problem` with an empty body.
+fn fill_empty_problem(mut response: Response, policy: EmptyProblem) -> Response {
+    let Some(problem) = response.extensions().get::<Problem>() else {
+        return response;
+    };
+    if !problem.response_body().is_empty() {
+        return response;
+    }
+    let Some((problem, body)) = policy.fill(problem) else {
+        return response;
+    };
+
+    response.set_body(body);
+    response.headers_mut().remove(CONTENT_LENGTH);
+    response.headers_mut().insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static(Format::ProblemJson.content_type()),
+    );
+    response.extensions_mut().insert(problem);
+
+    response
+}
+
+/// Render the `Problem` that a response was produced from in the given format, if there is one.
+fn render_response(mut response: Response, format: Format) -> Response {
+    let Some(problem) = response.extensions().get::<Problem>() else {
+        return response;
+    };
+    if problem.response_body().is_empty() {
+        return response;
+    }
+
+    let (format, body) = problem.render(format);
+    response.set_body(body);
+    response.headers_mut().remove(CONTENT_LENGTH);
+    response.headers_mut().insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static(format.content_type()),
+    );
+
+    response
+}
+
+/// Builder for a [`CatchPanic`] middleware that turns panics into problem responses.
+///
+/// # Examples
+/// ```
+/// # use http::StatusCode;
+/// use poem::{EndpointExt, Route};
+/// use problemdetails::poem::PanicHandlerBuilder;
+///
+/// let app = Route::new().with(
+///     PanicHandlerBuilder::new()
+///         .with_problem(problemdetails::new(StatusCode::SERVICE_UNAVAILABLE))
+///         .build(),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct PanicHandlerBuilder {
+    fill_detail: bool,
+    problem:     Problem,
+}
+
+impl Default for PanicHandlerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PanicHandlerBuilder {
+    /// Create a builder for a panic handler that sends a 500 Internal Server Error.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            fill_detail: crate::detail::fill_detail(),
+            problem:     crate::new(StatusCode::INTERNAL_SERVER_ERROR)
+                .with_title(crate::INTERNAL_SERVER_ERROR_TITLE),
         }
     }
+
+    /// Enable automatic setting of the `Problem` `detail` field to the panic message.
+    /// By default it is the value of [`crate::set_fill_detail`] when the builder is created.
+    #[must_use]
+    pub fn with_fill_detail(mut self, enabled: bool) -> Self {
+        self.fill_detail = enabled;
+
+        self
+    }
+
+    /// Set the base problem to be used by the panic handler.
+    /// If `fill_detail` is enabled, the `detail` field will be replaced with the panic message.
+    #[must_use]
+    pub fn with_problem(mut self, problem: Problem) -> Self {
+        self.problem = problem;
+
+        self
+    }
+
+    /// Build the panic handler.
+    #[must_use]
+    pub fn build(self) -> CatchPanic<Self> {
+        CatchPanic::new().with_handler(self)
+    }
+}
+
+impl PanicHandler for PanicHandlerBuilder {
+    type Response = Response;
+
+    fn get_response(&self, err: Box<dyn Any + Send + 'static>) -> Self::Response {
+        crate::panic::panic_problem(&self.problem, self.fill_detail, &*err).into_response()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use assert2::check;
-    use http::{header::CONTENT_TYPE, StatusCode};
+    use http::{
+        header::{ACCEPT, CONTENT_TYPE},
+        StatusCode,
+    };
     use insta::assert_json_snapshot;
-    use poem::{get, handler, test::TestClient, Route};
+    use poem::{error::NotFoundError, get, handler, test::TestClient, EndpointExt, Route};
 
     #[handler]
     fn no_value_handler() -> crate::Problem {
         crate::new(StatusCode::BAD_REQUEST)
     }
 
+    #[handler]
+    fn about_blank_handler() -> crate::Problem {
+        crate::new(StatusCode::NOT_FOUND).with_about_blank(crate::AboutBlank::FillTitle)
+    }
+
     #[handler]
     fn rfc7807_forbidden_example_handler() -> crate::Problem {
         crate::new(StatusCode::FORBIDDEN)
@@ -65,6 +373,14 @@ mod tests {
             )
     }
 
+    #[handler]
+    fn insert_status_member_handler() -> crate::Problem {
+        crate::new(StatusCode::NOT_FOUND)
+            .with_title("Not Found")
+            .with_value("status", 500)
+            .with_status_member(crate::StatusMember::Insert)
+    }
+
     #[tokio::test]
     async fn no_values() {
         let app = Route::new().at("/test", get(no_value_handler));
@@ -77,6 +393,30 @@ mod tests {
         check!(response.0.take_body().is_empty());
     }
 
+    #[tokio::test]
+    async fn about_blank() {
+        let app = Route::new().at("/test", get(about_blank_handler));
+        let cli = TestClient::new(app);
+
+        let mut response = cli.get("/test").send().await;
+
+        check!(response.0.status() == StatusCode::NOT_FOUND);
+        check!(response.0.content_type() == Some("application/problem+json"));
+
+        let body = response
+            .0
+            .take_body()
+            .into_json::<serde_json::Value>()
+            .await
+            .unwrap();
+
+        assert_json_snapshot!(body, @r###"
+        {
+          "title": "Not Found"
+        }
+        "###);
+    }
+
     #[tokio::test]
     async fn rfc7807_forbidden_example() {
         let app = Route::new().at("/test", get(rfc7807_forbidden_example_handler));
@@ -109,6 +449,31 @@ mod tests {
         "###);
     }
 
+    #[tokio::test]
+    async fn insert_status_member() {
+        let app = Route::new().at("/test", get(insert_status_member_handler));
+        let cli = TestClient::new(app);
+
+        let mut response = cli.get("/test").send().await;
+
+        check!(response.0.status() == StatusCode::NOT_FOUND);
+        check!(response.0.content_type() == Some("application/problem+json"));
+
+        let body = response
+            .0
+            .take_body()
+            .into_json::<serde_json::Value>()
+            .await
+            .unwrap();
+
+        assert_json_snapshot!(body, @r###"
+        {
+          "status": 404,
+          "title": "Not Found"
+        }
+        "###);
+    }
+
     #[tokio::test]
     async fn rfc7807_validation_example() {
         let app = Route::new().at("/test", get(rfc7807_validation_example_handler));
@@ -143,4 +508,250 @@ mod tests {
         }
         "###);
     }
+
+    #[handler]
+    fn negotiate_handler() -> poem::Result<()> {
+        Err(crate::new(StatusCode::NOT_FOUND)
+            .with_title("Not Found")
+            .with_detail("No user <1>")
+            .into())
+    }
+
+    fn negotiate_client(negotiator: crate::Negotiator) -> TestClient<impl poem::Endpoint> {
+        let app = Route::new()
+            .at("/test", get(rfc7807_forbidden_example_handler))
+            .at("/error", get(negotiate_handler))
+            .at("/empty", get(no_value_handler))
+            .with(super::NegotiateMiddleware::new(negotiator));
+
+        TestClient::new(app)
+    }
+
+    #[tokio::test]
+    async fn negotiate_default() {
+        let cli = negotiate_client(crate::Negotiator::new());
+
+        let response = cli.get("/test").send().await;
+
+        check!(response.0.status() == StatusCode::FORBIDDEN);
+        check!(response.0.content_type() == Some("application/problem+json"));
+    }
+
+    #[tokio::test]
+    async fn negotiate_html() {
+        let cli = negotiate_client(crate::Negotiator::new());
+
+        let mut response = cli
+            .get("/test")
+            .header(ACCEPT, "text/html,application/xhtml+xml,*/*;q=0.8")
+            .send()
+            .await;
+
+        check!(response.0.status() == StatusCode::FORBIDDEN);
+        check!(response.0.content_type() == Some("text/html; charset=utf-8"));
+        let body = response.0.take_body().into_string().await.unwrap();
+        check!(body.contains("<h1>You do not have enough credit.</h1>"));
+        check!(body.contains("<tr><td>balance</td><td>30</td></tr>"));
+    }
+
+    #[tokio::test]
+    async fn negotiate_error() {
+        let cli = negotiate_client(crate::Negotiator::new());
+
+        let mut response = cli.get("/error").header(ACCEPT, "text/plain").send().await;
+
+        check!(response.0.status() == StatusCode::NOT_FOUND);
+        check!(response.0.content_type() == Some("text/plain; charset=utf-8"));
+        let body = response.0.take_body().into_string().await.unwrap();
+        check!(body == "404 Not Found\ndetail: No user <1>\ntitle: Not Found\n");
+    }
+
+    #[tokio::test]
+    async fn negotiate_json_compatibility() {
+        let cli = negotiate_client(crate::Negotiator::new().with_json_compatibility(true));
+
+        let response = cli
+            .get("/test")
+            .header(ACCEPT, "application/json")
+            .send()
+            .await;
+
+        check!(response.0.content_type() == Some("application/json"));
+    }
+
+    #[tokio::test]
+    async fn negotiate_empty_problem() {
+        let cli = negotiate_client(crate::Negotiator::new());
+
+        let mut response = cli.get("/empty").header(ACCEPT, "text/html").send().await;
+
+        check!(response.0.status() == StatusCode::BAD_REQUEST);
+        check!(response.0.headers().get(CONTENT_TYPE) == None);
+        check!(response.0.take_body().is_empty());
+    }
+
+    #[tokio::test]
+    async fn documentation_route() {
+        let registry = crate::ProblemTypeRegistry::new().with_type(
+            "https://example.com/probs/out-of-credit",
+            StatusCode::FORBIDDEN,
+            "You do not have enough credit.",
+            "The account does not have enough credit to pay for the request.",
+        );
+        let cli = TestClient::new(super::documentation_route(&registry));
+
+        let mut response = cli.get("/probs/out-of-credit").send().await;
+
+        check!(response.0.status() == StatusCode::OK);
+        check!(response.0.content_type() == Some("text/html; charset=utf-8"));
+        let body = response.0.take_body().into_string().await.unwrap();
+        check!(
+            body == registry
+                .get("https://example.com/probs/out-of-credit")
+                .unwrap()
+                .documentation()
+        );
+
+        let response = cli.get("/probs/unknown").send().await;
+        check!(response.0.status() == StatusCode::NOT_FOUND);
+    }
+
+    fn empty_problem_client(policy: crate::EmptyProblem) -> TestClient<impl poem::Endpoint> {
+        let app = Route::new()
+            .at("/empty", get(no_value_handler))
+            .with(super::EmptyProblemMiddleware::new(policy))
+            .with(super::NegotiateMiddleware::default());
+
+        TestClient::new(app)
+    }
+
+    #[tokio::test]
+    async fn empty_problem_status_only() {
+        let cli = empty_problem_client(crate::EmptyProblem::StatusOnly);
+
+        let mut response = cli.get("/empty").send().await;
+
+        check!(response.0.status() == StatusCode::BAD_REQUEST);
+        check!(response.0.headers().get(CONTENT_TYPE) == None);
+        check!(response.0.take_body().is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_problem_empty_object() {
+        let cli = empty_problem_client(crate::EmptyProblem::EmptyObject);
+
+        let mut response = cli.get("/empty").send().await;
+
+        check!(response.0.status() == StatusCode::BAD_REQUEST);
+        check!(response.0.content_type() == Some("application/problem+json"));
+        check!(response.0.take_body().into_string().await.unwrap() == "{}");
+    }
+
+    #[tokio::test]
+    async fn other_errors_pass_through() {
+        let app = Route::new()
+            .at("/empty", get(no_value_handler))
+            .with(super::EmptyProblemMiddleware::new(
+                crate::EmptyProblem::AboutBlank,
+            ))
+            .with(super::NegotiateMiddleware::default())
+            .catch_error(|_: NotFoundError| async { "Caught" });
+        let cli = TestClient::new(app);
+
+        let mut response = cli.get("/missing").send().await;
+
+        check!(response.0.status() == StatusCode::OK);
+        check!(response.0.take_body().into_string().await.unwrap() == "Caught");
+    }
+
+    #[tokio::test]
+    async fn empty_problem_about_blank() {
+        let cli = empty_problem_client(crate::EmptyProblem::AboutBlank);
+
+        let mut response = cli.get("/empty").send().await;
+
+        check!(response.0.status() == StatusCode::BAD_REQUEST);
+        check!(response.0.content_type() == Some("application/problem+json"));
+        check!(response.0.take_body().into_string().await.unwrap() == r#"{"title":"Bad Request"}"#);
+
+        let mut response = cli.get("/empty").header(ACCEPT, "text/html").send().await;
+
+        check!(response.0.content_type() == Some("text/html; charset=utf-8"));
+        check!(response
+            .0
+            .take_body()
+            .into_string()
+            .await
+            .unwrap()
+            .contains("<h1>Bad Request</h1>"));
+    }
+
+    #[handler]
+    fn panicking_handler() {
+        panic!("Panic message");
+    }
+
+    async fn panic_response(
+        builder: crate::poem::PanicHandlerBuilder,
+    ) -> (StatusCode, serde_json::Value) {
+        let app = Route::new()
+            .at("/panic", get(panicking_handler))
+            .with(builder.build());
+        let cli = TestClient::new(app);
+
+        let mut response = cli.get("/panic").send().await;
+
+        check!(response.0.content_type() == Some("application/problem+json"));
+        let body = response
+            .0
+            .take_body()
+            .into_json::<serde_json::Value>()
+            .await
+            .unwrap();
+
+        (response.0.status(), body)
+    }
+
+    #[tokio::test]
+    async fn default_panic() {
+        let (status, body) = panic_response(crate::poem::PanicHandlerBuilder::new()).await;
+
+        check!(status == StatusCode::INTERNAL_SERVER_ERROR);
+        assert_json_snapshot!(body, @r###"
+        {
+          "detail": "Panic message",
+          "title": "Internal server error"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn fill_panic_message_false() {
+        let (status, body) =
+            panic_response(crate::poem::PanicHandlerBuilder::new().with_fill_detail(false)).await;
+
+        check!(status == StatusCode::INTERNAL_SERVER_ERROR);
+        assert_json_snapshot!(body, @r###"
+        {
+          "title": "Internal server error"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn customized_panic() {
+        let (status, body) = panic_response(
+            crate::poem::PanicHandlerBuilder::new()
+                .with_problem(crate::new(StatusCode::IM_A_TEAPOT).with_instance("some instance")),
+        )
+        .await;
+
+        check!(status == StatusCode::IM_A_TEAPOT);
+        assert_json_snapshot!(body, @r###"
+        {
+          "detail": "Panic message",
+          "instance": "some instance"
+        }
+        "###);
+    }
 }
@@ -0,0 +1,169 @@
+use http::StatusCode;
+
+use super::Problem;
+
+/// A kind of problem, with the "type" URI, "title" and status code that every problem of this
+/// kind shares.
+///
+/// This can be declared as a `const` or `static`, so that the kinds of problem used by an API are
+/// defined once and can be found by name.
+///
+/// # Examples
+/// ```
+/// # use http::StatusCode;
+/// use problemdetails::ProblemType;
+///
+/// const OUT_OF_CREDIT: ProblemType = ProblemType::new(
+///     "https://example.com/probs/out-of-credit",
+///     StatusCode::FORBIDDEN,
+///     "You do not have enough credit.",
+/// );
+///
+/// let problem = OUT_OF_CREDIT
+///     .with_detail("Your current balance is 30, but that costs 50.")
+///     .with_value("balance", 30);
+///
+/// assert_eq!(problem.status_code, StatusCode::FORBIDDEN);
+/// assert!(problem.is(&OUT_OF_CREDIT));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProblemType {
+    uri:         &'static str,
+    status_code: StatusCode,
+    title:       &'static str,
+}
+
+impl ProblemType {
+    /// Define a new kind of problem.
+    ///
+    /// # Parameters
+    /// - `uri` - The URI to use as the "type" of the problem.
+    /// - `status_code` - The status code for problems of this type.
+    /// - `title` - The "title" for problems of this type.
+    #[must_use]
+    pub const fn new(uri: &'static str, status_code: StatusCode, title: &'static str) -> Self {
+        Self {
+            uri,
+            status_code,
+            title,
+        }
+    }
+
+    /// Get the URI that is used as the "type" of the problem.
+    #[must_use]
+    pub const fn uri(&self) -> &'static str {
+        self.uri
+    }
+
+    /// Get the status code for problems of this type.
+    #[must_use]
+    pub const fn status_code(&self) -> StatusCode {
+        self.status_code
+    }
+
+    /// Get the "title" for problems of this type.
+    #[must_use]
+    pub const fn title(&self) -> &'static str {
+        self.title
+    }
+
+    /// Create a new `Problem` of this type, with the "type", "title" and status code filled in.
+    #[must_use]
+    pub fn problem(&self) -> Problem {
+        crate::new(self.status_code)
+            .with_type(self.uri)
+            .with_title(self.title)
+    }
+
+    /// Create a new `Problem` of this type with the given "detail".
+    #[must_use]
+    pub fn with_detail<S>(&self, detail: S) -> Problem
+    where
+        S: Into<String>,
+    {
+        self.problem().with_detail(detail)
+    }
+}
+
+impl From<ProblemType> for Problem {
+    fn from(problem_type: ProblemType) -> Self {
+        problem_type.problem()
+    }
+}
+
+impl From<&ProblemType> for Problem {
+    fn from(problem_type: &ProblemType) -> Self {
+        problem_type.problem()
+    }
+}
+
+impl Problem {
+    /// Check if this problem is of the given type, by comparing the "type" URI.
+    #[must_use]
+    pub fn is(&self, problem_type: &ProblemType) -> bool {
+        self.problem_type() == Some(problem_type.uri)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use http::StatusCode;
+    use insta::assert_json_snapshot;
+
+    use super::ProblemType;
+    use crate::Problem;
+
+    const OUT_OF_CREDIT: ProblemType = ProblemType::new(
+        "https://example.com/probs/out-of-credit",
+        StatusCode::FORBIDDEN,
+        "You do not have enough credit.",
+    );
+
+    static ACCOUNT_LOCKED: ProblemType = ProblemType::new(
+        "https://example.com/probs/account-locked",
+        StatusCode::LOCKED,
+        "Account locked",
+    );
+
+    #[test]
+    fn problem() {
+        let problem = OUT_OF_CREDIT
+            .with_detail("Your current balance is 30, but that costs 50.")
+            .with_value("balance", 30);
+
+        check!(problem.status_code == StatusCode::FORBIDDEN);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "balance": 30,
+          "detail": "Your current balance is 30, but that costs 50.",
+          "title": "You do not have enough credit.",
+          "type": "https://example.com/probs/out-of-credit"
+        }
+        "###);
+    }
+
+    #[test]
+    fn from_problem_type() {
+        let problem = Problem::from(&ACCOUNT_LOCKED);
+
+        check!(problem.status_code == StatusCode::LOCKED);
+        check!(problem.title() == Some("Account locked"));
+    }
+
+    #[test]
+    fn is() {
+        check!(OUT_OF_CREDIT.problem().is(&OUT_OF_CREDIT));
+        check!(!OUT_OF_CREDIT.problem().is(&ACCOUNT_LOCKED));
+        check!(!crate::new(StatusCode::FORBIDDEN).is(&OUT_OF_CREDIT));
+    }
+
+    #[test]
+    fn is_ignores_status_and_title() {
+        let problem = crate::new(StatusCode::PAYMENT_REQUIRED)
+            .with_type(OUT_OF_CREDIT.uri())
+            .with_title("Something else");
+
+        check!(problem.is(&OUT_OF_CREDIT));
+    }
+}
@@ -0,0 +1,359 @@
+use std::collections::BTreeMap;
+
+use http::{StatusCode, Uri};
+
+use super::{html::escape, Problem, ProblemType};
+
+/// Details of a problem type that has been registered with a [`ProblemTypeRegistry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisteredType {
+    uri:         String,
+    status_code: StatusCode,
+    title:       String,
+    description: String,
+}
+
+impl RegisteredType {
+    /// Get the URI that is used as the "type" of the problem.
+    #[must_use]
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Get the default status code for problems of this type.
+    #[must_use]
+    pub fn status_code(&self) -> StatusCode {
+        self.status_code
+    }
+
+    /// Get the default "title" for problems of this type.
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Get the human-readable description of this type.
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Create a new `Problem` of this type, with the default status code and title.
+    #[must_use]
+    pub fn problem(&self) -> Problem {
+        crate::new(self.status_code)
+            .with_type(&self.uri)
+            .with_title(&self.title)
+    }
+
+    /// Get the path that the documentation for this type is served from, if the URI is either an
+    /// HTTP URI or a path.
+    #[must_use]
+    pub fn documentation_path(&self) -> Option<String> {
+        let uri: Uri = self.uri.parse().ok()?;
+
+        match uri.scheme_str() {
+            None | Some("http" | "https") if uri.path().starts_with('/') => {
+                Some(uri.path().to_owned())
+            },
+            _ => None,
+        }
+    }
+
+    /// Render the documentation for this type as an HTML page.
+    #[must_use]
+    pub fn documentation(&self) -> String {
+        let title = escape(&self.title);
+
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n</head>\n<body>\n<h1>{title}</h1>\n\
+             <dl>\n<dt>Type</dt>\n<dd>{}</dd>\n<dt>Status</dt>\n<dd>{}</dd>\n</dl>\n\
+             <p>{}</p>\n</body>\n</html>\n",
+            escape(&self.uri),
+            escape(&self.status_code.to_string()),
+            escape(&self.description),
+        )
+    }
+}
+
+/// A catalog of the problem types used by an API, so that each type is only described in one
+/// place.
+///
+/// Problems are built from a registered type, which fills in the "type" and "title". RFC-9457
+/// recommends that the "type" URI resolves to human-readable documentation, and the documentation
+/// for every registered type can be served from `axum::documentation_router` or
+/// `poem::documentation_route`.
+///
+/// # Examples
+/// ```
+/// # use http::StatusCode;
+/// use problemdetails::ProblemTypeRegistry;
+///
+/// const OUT_OF_CREDIT: &str = "https://example.com/probs/out-of-credit";
+///
+/// let registry = ProblemTypeRegistry::new().with_type(
+///     OUT_OF_CREDIT,
+///     StatusCode::FORBIDDEN,
+///     "You do not have enough credit.",
+///     "The account does not have enough credit to pay for the request.",
+/// );
+///
+/// let problem = registry
+///     .problem(OUT_OF_CREDIT)
+///     .unwrap()
+///     .with_detail("Your current balance is 30, but that costs 50.");
+/// assert_eq!(problem.status_code, StatusCode::FORBIDDEN);
+/// assert_eq!(problem.title(), Some("You do not have enough credit."));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ProblemTypeRegistry {
+    types: BTreeMap<String, RegisteredType>,
+}
+
+impl ProblemTypeRegistry {
+    /// Create an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a problem type. If the URI was already registered, it is replaced.
+    ///
+    /// # Parameters
+    /// - `uri` - The URI to use as the "type" of the problem.
+    /// - `status_code` - The default status code for problems of this type.
+    /// - `title` - The default "title" for problems of this type.
+    /// - `description` - A human-readable description of the type, for the documentation.
+    #[must_use]
+    pub fn with_type<U, T, D>(
+        mut self,
+        uri: U,
+        status_code: StatusCode,
+        title: T,
+        description: D,
+    ) -> Self
+    where
+        U: Into<String>,
+        T: Into<String>,
+        D: Into<String>,
+    {
+        let uri = uri.into();
+        self.types.insert(
+            uri.clone(),
+            RegisteredType {
+                uri,
+                status_code,
+                title: title.into(),
+                description: description.into(),
+            },
+        );
+
+        self
+    }
+
+    /// Register a problem type that is defined as a [`ProblemType`], along with its description.
+    /// If the URI was already registered, it is replaced.
+    #[must_use]
+    pub fn with_problem_type<D>(self, problem_type: &ProblemType, description: D) -> Self
+    where
+        D: Into<String>,
+    {
+        self.with_type(
+            problem_type.uri(),
+            problem_type.status_code(),
+            problem_type.title(),
+            description,
+        )
+    }
+
+    /// Get the details of a registered type.
+    #[must_use]
+    pub fn get(&self, uri: &str) -> Option<&RegisteredType> {
+        self.types.get(uri)
+    }
+
+    /// Create a new `Problem` of a registered type, with its default status code and title.
+    ///
+    /// Returns `None` if the type has not been registered.
+    #[must_use]
+    pub fn problem(&self, uri: &str) -> Option<Problem> {
+        self.get(uri).map(RegisteredType::problem)
+    }
+
+    /// Iterate over every registered type, ordered by URI.
+    pub fn iter(&self) -> impl Iterator<Item = &RegisteredType> {
+        self.types.values()
+    }
+
+    /// Get the documentation page for every type that can be served, keyed by path.
+    ///
+    /// If several types have the same path then only the first one is used. Paths that routers
+    /// would treat as patterns, such as `/probs/:id` or `/probs/{id}`, are not served.
+    #[allow(dead_code)] // This is used by the server features.
+    pub(crate) fn documentation_pages(&self) -> BTreeMap<String, String> {
+        let mut pages = BTreeMap::new();
+
+        for registered in self.iter() {
+            if let Some(path) = registered
+                .documentation_path()
+                .filter(|path| is_literal_path(path))
+            {
+                pages
+                    .entry(path)
+                    .or_insert_with(|| registered.documentation());
+            }
+        }
+
+        pages
+    }
+}
+
+/// Check that a path has no segments that routers would treat as a pattern.
+fn is_literal_path(path: &str) -> bool {
+    path.split('/')
+        .all(|segment| !segment.starts_with([':', '*']) && !segment.contains(['{', '}', '<', '>']))
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{assert, check};
+    use http::StatusCode;
+    use insta::{assert_json_snapshot, assert_snapshot};
+
+    use super::{ProblemTypeRegistry, RegisteredType};
+    use crate::ProblemType;
+
+    const OUT_OF_CREDIT: &str = "https://example.com/probs/out-of-credit";
+
+    fn registry() -> ProblemTypeRegistry {
+        ProblemTypeRegistry::new()
+            .with_type(
+                OUT_OF_CREDIT,
+                StatusCode::FORBIDDEN,
+                "You do not have enough credit.",
+                "The account does not have enough credit to pay for the request.",
+            )
+            .with_type(
+                "/probs/locked",
+                StatusCode::LOCKED,
+                "Account <locked>",
+                "The account is locked & can't be used.",
+            )
+            .with_type(
+                "tag:example.com,2026:conflict",
+                StatusCode::CONFLICT,
+                "Conflict",
+                "Somebody else changed it first.",
+            )
+    }
+
+    #[test]
+    fn problem_from_registered_type() {
+        let problem = registry()
+            .problem(OUT_OF_CREDIT)
+            .unwrap()
+            .with_detail("Your current balance is 30, but that costs 50.");
+
+        check!(problem.status_code == StatusCode::FORBIDDEN);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "detail": "Your current balance is 30, but that costs 50.",
+          "title": "You do not have enough credit.",
+          "type": "https://example.com/probs/out-of-credit"
+        }
+        "###);
+    }
+
+    #[test]
+    fn registered_problem_type() {
+        const ACCOUNT_LOCKED: ProblemType = ProblemType::new(
+            "https://example.com/probs/account-locked",
+            StatusCode::LOCKED,
+            "Account locked",
+        );
+
+        let registry =
+            ProblemTypeRegistry::new().with_problem_type(&ACCOUNT_LOCKED, "The account is locked.");
+
+        assert!(let Some(problem) = registry.problem(ACCOUNT_LOCKED.uri()));
+        check!(problem.is(&ACCOUNT_LOCKED));
+        check!(problem.status_code == StatusCode::LOCKED);
+        check!(
+            registry.get(ACCOUNT_LOCKED.uri()).unwrap().description() == "The account is locked."
+        );
+    }
+
+    #[test]
+    fn unknown_type() {
+        check!(registry()
+            .problem("https://example.com/probs/unknown")
+            .is_none());
+    }
+
+    #[test]
+    fn replace_type() {
+        let registry = registry().with_type(OUT_OF_CREDIT, StatusCode::PAYMENT_REQUIRED, "A", "B");
+
+        assert!(let Some(registered) = registry.get(OUT_OF_CREDIT));
+        check!(registered.status_code() == StatusCode::PAYMENT_REQUIRED);
+        check!(registered.title() == "A");
+        check!(registered.description() == "B");
+    }
+
+    #[test]
+    fn documentation_paths() {
+        let paths: Vec<_> = registry()
+            .iter()
+            .map(RegisteredType::documentation_path)
+            .collect();
+
+        check!(
+            paths
+                == vec![
+                    Some("/probs/locked".to_owned()),
+                    Some("/probs/out-of-credit".to_owned()),
+                    None,
+                ]
+        );
+    }
+
+    #[test]
+    fn documentation_pages() {
+        let registry = registry().with_type(
+            "https://example.com/probs/:id",
+            StatusCode::NOT_FOUND,
+            "Pattern",
+            "Looks like a route pattern.",
+        );
+        let paths: Vec<_> = registry.documentation_pages().into_keys().collect();
+
+        check!(paths == vec!["/probs/locked", "/probs/out-of-credit"]);
+    }
+
+    #[test]
+    fn documentation() {
+        let registry = registry();
+        let registered = registry.get("/probs/locked").unwrap();
+
+        assert_snapshot!(registered.documentation(), @r###"
+        <!DOCTYPE html>
+        <html>
+        <head>
+        <meta charset="utf-8">
+        <title>Account &lt;locked&gt;</title>
+        </head>
+        <body>
+        <h1>Account &lt;locked&gt;</h1>
+        <dl>
+        <dt>Type</dt>
+        <dd>/probs/locked</dd>
+        <dt>Status</dt>
+        <dd>423 Locked</dd>
+        </dl>
+        <p>The account is locked &amp; can&#39;t be used.</p>
+        </body>
+        </html>
+        "###);
+    }
+}
@@ -0,0 +1,113 @@
+use http::StatusCode;
+
+use super::{Problem, ProblemType};
+
+/// Build a problem with the reason phrase of the status code as the `title`.
+#[allow(dead_code)] // This is used by the server features.
+pub(crate) fn status_problem(status_code: StatusCode) -> Problem {
+    let problem = crate::new(status_code);
+
+    match status_code.canonical_reason() {
+        Some(title) => problem.with_title(title),
+        None => problem,
+    }
+}
+
+/// Build the problem for a request that an HTTP Server rejected, keeping the status code that the
+/// server chose.
+///
+/// Rejections without a known type get the reason phrase of the status code as the `title`. The
+/// message of the rejection is only used as the `detail` of server errors if `fill_detail` is
+/// enabled, because these can contain internal details.
+#[allow(dead_code)] // This is used by the server features.
+pub(crate) fn rejection_problem(
+    problem_type: Option<&ProblemType>,
+    status_code: StatusCode,
+    message: String,
+    fill_detail: bool,
+) -> Problem {
+    let mut problem =
+        problem_type.map_or_else(|| status_problem(status_code), ProblemType::problem);
+    problem.status_code = status_code;
+
+    if status_code.is_server_error() && !fill_detail {
+        problem
+    } else {
+        problem.with_detail(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use http::StatusCode;
+
+    use super::{rejection_problem, status_problem};
+    use crate::types::{INVALID_PATH_PARAMS, JSON_SYNTAX_ERROR};
+
+    #[test]
+    fn status_title() {
+        check!(status_problem(StatusCode::NOT_FOUND).title() == Some("Not Found"));
+        check!(status_problem(StatusCode::from_u16(599).unwrap()).title() == None);
+    }
+
+    #[test]
+    fn unknown_type() {
+        let problem = rejection_problem(
+            None,
+            StatusCode::METHOD_NOT_ALLOWED,
+            "Method not allowed".to_owned(),
+            false,
+        );
+
+        check!(problem.status_code == StatusCode::METHOD_NOT_ALLOWED);
+        check!(problem.title() == Some("Method Not Allowed"));
+        check!(problem.detail() == Some("Method not allowed"));
+    }
+
+    #[test]
+    fn keeps_status_code() {
+        let problem = rejection_problem(
+            Some(&INVALID_PATH_PARAMS),
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Wrong number of path arguments".to_owned(),
+            true,
+        );
+
+        check!(problem.status_code == StatusCode::INTERNAL_SERVER_ERROR);
+        check!(problem.is(&INVALID_PATH_PARAMS));
+    }
+
+    #[test]
+    fn server_error_detail() {
+        let message = "Wrong number of path arguments";
+
+        let problem = rejection_problem(
+            Some(&INVALID_PATH_PARAMS),
+            StatusCode::INTERNAL_SERVER_ERROR,
+            message.to_owned(),
+            false,
+        );
+        check!(problem.detail() == None);
+
+        let problem = rejection_problem(
+            Some(&INVALID_PATH_PARAMS),
+            StatusCode::INTERNAL_SERVER_ERROR,
+            message.to_owned(),
+            true,
+        );
+        check!(problem.detail() == Some(message));
+    }
+
+    #[test]
+    fn client_error_detail() {
+        let problem = rejection_problem(
+            Some(&JSON_SYNTAX_ERROR),
+            StatusCode::BAD_REQUEST,
+            "Failed to parse the request body as JSON".to_owned(),
+            false,
+        );
+
+        check!(problem.detail() == Some("Failed to parse the request body as JSON"));
+    }
+}
@@ -0,0 +1,254 @@
+//! Conversion of error reports from [anyhow](https://crates.io/crates/anyhow) and
+//! [eyre](https://crates.io/crates/eyre) into a `Problem`.
+//!
+//! By default, any report becomes a 500 Internal Server Error. The message of the report is only
+//! used as the `detail` in debug builds, so that internal details are not leaked in release builds.
+//! This can be changed with [`set_fill_detail`].
+//!
+//! The response can be decided by the errors in the chain of the report instead. If there is a
+//! `Problem` anywhere in the chain then it is used as-is. Otherwise, error types can be registered
+//! with [`register_error`] to convert them into a `Problem`.
+//!
+//! # Examples
+//! ```
+//! # use http::StatusCode;
+//! # #[cfg(feature = "anyhow")]
+//! # {
+//! #[derive(Debug)]
+//! struct NotFound(u32);
+//!
+//! impl std::fmt::Display for NotFound {
+//!     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+//!         write!(f, "No user with ID {}", self.0)
+//!     }
+//! }
+//!
+//! impl std::error::Error for NotFound {}
+//!
+//! problemdetails::report::register_error(|err: &NotFound| {
+//!     problemdetails::new(StatusCode::NOT_FOUND).with_detail(err.to_string())
+//! });
+//!
+//! let err = anyhow::Error::new(NotFound(1)).context("Loading user");
+//! let problem = problemdetails::Problem::from(err);
+//! assert_eq!(problem.status_code, StatusCode::NOT_FOUND);
+//! # }
+//! ```
+
+use std::{
+    error::Error,
+    sync::{Arc, RwLock},
+};
+
+use http::StatusCode;
+
+use super::Problem;
+
+/// A registered conversion from an error in the chain of a report into a `Problem`.
+type Converter = Box<dyn Fn(&(dyn Error + 'static)) -> Option<Problem> + Send + Sync>;
+
+/// The conversions that have been registered with [`register_error`].
+static CONVERTERS: RwLock<Vec<Converter>> = RwLock::new(Vec::new());
+
+/// Register a conversion into a `Problem` for an error type that might be found in the chain of a
+/// report.
+///
+/// Conversions are tried in the order they were registered, against each error in the chain in
+/// turn, starting from the outermost one.
+pub fn register_error<E, F>(convert: F)
+where
+    E: Error + 'static,
+    F: Fn(&E) -> Problem + Send + Sync + 'static,
+{
+    CONVERTERS
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .push(Box::new(move |err| err.downcast_ref::<E>().map(&convert)));
+}
+
+/// Enable setting the `detail` of the default `Problem` to the message of the report. This is
+/// the same setting as [`crate::set_fill_detail`], which also applies to other server errors.
+pub use crate::detail::set_fill_detail;
+
+/// Build the `Problem` for a report, given the chain of errors in it.
+fn problem_for_chain<'a, I>(chain: I) -> Result<Problem, Vec<String>>
+where
+    I: IntoIterator<Item = &'a (dyn Error + 'static)>,
+{
+    let converters = CONVERTERS
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+    let mut messages = vec![];
+    for err in chain {
+        if let Some(problem) = err.downcast_ref::<Problem>() {
+            return Ok(problem.clone());
+        }
+        if let Some(problem) = converters.iter().find_map(|convert| convert(err)) {
+            return Ok(problem);
+        }
+        messages.push(err.to_string());
+    }
+
+    Err(messages)
+}
+
+/// Build the default `Problem` for a report that nothing else handled.
+fn default_problem(messages: &[String], source: Box<dyn Error + Send + Sync>) -> Problem {
+    let mut problem = crate::new(StatusCode::INTERNAL_SERVER_ERROR)
+        .with_title(crate::INTERNAL_SERVER_ERROR_TITLE);
+
+    if crate::detail::fill_detail() {
+        problem = problem.with_detail(messages.join(": "));
+    }
+    problem.source = Some(Arc::from(source));
+
+    problem
+}
+
+#[cfg(feature = "anyhow")]
+impl From<anyhow::Error> for Problem {
+    fn from(err: anyhow::Error) -> Self {
+        match problem_for_chain(err.chain().map(|err| err as &(dyn Error + 'static))) {
+            Ok(problem) => problem,
+            Err(messages) => default_problem(&messages, err.into()),
+        }
+    }
+}
+
+#[cfg(feature = "eyre")]
+impl From<eyre::Report> for Problem {
+    fn from(err: eyre::Report) -> Self {
+        match problem_for_chain(err.chain().map(|err| err as &(dyn Error + 'static))) {
+            Ok(problem) => problem,
+            Err(messages) => default_problem(&messages, err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fmt, sync::Once};
+
+    use assert2::check;
+    use http::StatusCode;
+    use insta::assert_json_snapshot;
+
+    use crate::Problem;
+
+    #[derive(Debug)]
+    struct Registered(u32);
+
+    impl fmt::Display for Registered {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "No user with ID {}", self.0)
+        }
+    }
+
+    impl Error for Registered {}
+
+    /// Register the converter for [`Registered`], only once for all of the tests, because the
+    /// registry is global.
+    fn register() {
+        static REGISTER: Once = Once::new();
+
+        REGISTER.call_once(|| {
+            super::register_error(|err: &Registered| {
+                crate::new(StatusCode::NOT_FOUND)
+                    .with_title("Not Found")
+                    .with_value("id", err.0)
+            });
+        });
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[test]
+    fn anyhow_default() {
+        let problem = Problem::from(anyhow::anyhow!("Connection refused").context("Loading user"));
+
+        check!(problem.status_code == StatusCode::INTERNAL_SERVER_ERROR);
+        check!(problem.source().map(ToString::to_string).as_deref() == Some("Loading user"));
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "detail": "Loading user: Connection refused",
+          "title": "Internal server error"
+        }
+        "###);
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[test]
+    fn anyhow_problem_in_chain() {
+        let err = anyhow::Error::new(crate::new(StatusCode::CONFLICT).with_title("Conflict"))
+            .context("Saving user");
+        let problem = Problem::from(err);
+
+        check!(problem.status_code == StatusCode::CONFLICT);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "title": "Conflict"
+        }
+        "###);
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[test]
+    fn anyhow_registered_error_in_chain() {
+        register();
+
+        let problem = Problem::from(anyhow::Error::new(Registered(1)).context("Loading user"));
+
+        check!(problem.status_code == StatusCode::NOT_FOUND);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "id": 1,
+          "title": "Not Found"
+        }
+        "###);
+    }
+
+    #[cfg(feature = "eyre")]
+    #[test]
+    fn eyre_default() {
+        let problem = Problem::from(eyre::eyre!("Connection refused").wrap_err("Loading user"));
+
+        check!(problem.status_code == StatusCode::INTERNAL_SERVER_ERROR);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "detail": "Loading user: Connection refused",
+          "title": "Internal server error"
+        }
+        "###);
+    }
+
+    #[cfg(feature = "eyre")]
+    #[test]
+    fn eyre_problem_in_chain() {
+        let err = eyre::Report::new(crate::new(StatusCode::CONFLICT).with_title("Conflict"))
+            .wrap_err("Saving user");
+        let problem = Problem::from(err);
+
+        check!(problem.status_code == StatusCode::CONFLICT);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "title": "Conflict"
+        }
+        "###);
+    }
+
+    #[cfg(feature = "eyre")]
+    #[test]
+    fn eyre_registered_error_in_chain() {
+        register();
+
+        let problem = Problem::from(eyre::Report::new(Registered(2)).wrap_err("Loading user"));
+
+        check!(problem.status_code == StatusCode::NOT_FOUND);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "id": 2,
+          "title": "Not Found"
+        }
+        "###);
+    }
+}
@@ -0,0 +1,243 @@
+use std::future::Future;
+
+use bytes::Bytes;
+use http::{header::CONTENT_TYPE, StatusCode};
+
+use super::Problem;
+use crate::client::is_problem_json;
+
+/// Extension trait for turning error responses from [`reqwest`] into a `Problem`.
+pub trait ResponseExt: Sized {
+    /// Turn an unsuccessful response into a `Problem`, so that it can be returned from a handler
+    /// with `?`.
+    ///
+    /// Any response with a status code outside of the 2xx range is an error. If it has an
+    /// `application/problem+json` body then this is parsed into the `Problem`. Otherwise, a
+    /// `Problem` is synthesized from the status code of the response, with the body text as the
+    /// `detail`.
+    ///
+    /// If the body of the response can not be read, or claims to be a problem but can not be
+    /// parsed as one, then the `Problem` is a 502 Bad Gateway instead. The error is kept as the
+    /// source of the problem, so that it can be logged.
+    ///
+    /// Successful responses are returned unchanged.
+    ///
+    /// # Errors
+    /// If the response has a status code outside of the 2xx range.
+    fn error_for_problem(self) -> impl Future<Output = crate::Result<Self>> + Send;
+}
+
+impl ResponseExt for reqwest::Response {
+    async fn error_for_problem(self) -> crate::Result<Self> {
+        let status_code = self.status();
+        if status_code.is_success() {
+            return Ok(self);
+        }
+
+        let content_type = self.headers().get(CONTENT_TYPE).cloned();
+        let body = self.bytes().await.map_err(bad_gateway)?;
+
+        let problem = match content_type.filter(is_problem_json) {
+            Some(content_type) => {
+                let response = http::Response::builder()
+                    .status(status_code)
+                    .header(CONTENT_TYPE, content_type)
+                    .body(body)
+                    .expect("Could not rebuild the response for the problem detail");
+
+                Problem::from_response(response).map_err(bad_gateway)?
+            },
+            None => synthesize(status_code, &body),
+        };
+
+        Err(problem)
+    }
+}
+
+/// Build a `Problem` for an error response that did not contain one.
+fn synthesize(status_code: StatusCode, body: &Bytes) -> Problem {
+    let detail = String::from_utf8_lossy(body);
+
+    if detail.trim().is_empty() {
+        crate::new(status_code)
+    } else {
+        crate::new(status_code).with_detail(detail)
+    }
+}
+
+/// Build the `Problem` for an error response that could not be read or parsed.
+fn bad_gateway<E>(err: E) -> Problem
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    crate::rejection::status_problem(StatusCode::BAD_GATEWAY).with_source(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{convert::Infallible, error::Error};
+
+    use assert2::{assert, check};
+    use bytes::Bytes;
+    use http::{header::CONTENT_TYPE, StatusCode};
+    use http_body_util::Full;
+    use hyper::{server::conn::http1, service::service_fn};
+    use hyper_util::rt::TokioIo;
+    use insta::assert_json_snapshot;
+    use tokio::net::TcpListener;
+
+    use super::ResponseExt;
+    use crate::FromResponseError;
+
+    /// Start a local server that always returns the given response, and get its URL.
+    async fn serve(status: StatusCode, content_type: &'static str, body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+
+                tokio::spawn(async move {
+                    let service = service_fn(move |_| async move {
+                        Ok::<_, Infallible>(
+                            http::Response::builder()
+                                .status(status)
+                                .header(CONTENT_TYPE, content_type)
+                                .body(Full::new(Bytes::from_static(body.as_bytes())))
+                                .unwrap(),
+                        )
+                    });
+
+                    http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await
+                        .unwrap();
+                });
+            }
+        });
+
+        format!("http://{address}/")
+    }
+
+    #[tokio::test]
+    async fn success() {
+        let url = serve(StatusCode::OK, "text/plain", "Hello").await;
+
+        let response = reqwest::get(url).await.unwrap().error_for_problem().await;
+
+        assert!(let Ok(response) = response);
+        check!(response.status() == StatusCode::OK);
+        check!(response.text().await.unwrap() == "Hello");
+    }
+
+    #[tokio::test]
+    async fn rfc7807_forbidden_example() {
+        let url = serve(
+            StatusCode::FORBIDDEN,
+            "application/problem+json",
+            r#"{
+                "type": "https://example.com/probs/out-of-credit",
+                "title": "You do not have enough credit.",
+                "status": 403,
+                "detail": "Your current balance is 30, but that costs 50.",
+                "instance": "/account/12345/msgs/abc",
+                "balance": 30,
+                "accounts": ["/account/12345", "/account/67890"]
+            }"#,
+        )
+        .await;
+
+        let response = reqwest::get(url).await.unwrap().error_for_problem().await;
+
+        assert!(let Err(problem) = response);
+        check!(problem.status_code == StatusCode::FORBIDDEN);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "accounts": [
+            "/account/12345",
+            "/account/67890"
+          ],
+          "balance": 30,
+          "detail": "Your current balance is 30, but that costs 50.",
+          "instance": "/account/12345/msgs/abc",
+          "title": "You do not have enough credit.",
+          "type": "https://example.com/probs/out-of-credit"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn plain_text_error() {
+        let url = serve(
+            StatusCode::BAD_GATEWAY,
+            "text/plain",
+            "Upstream unavailable",
+        )
+        .await;
+
+        let response = reqwest::get(url).await.unwrap().error_for_problem().await;
+
+        assert!(let Err(problem) = response);
+        check!(problem.status_code == StatusCode::BAD_GATEWAY);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "detail": "Upstream unavailable"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn empty_error() {
+        let url = serve(StatusCode::NOT_FOUND, "text/plain", "").await;
+
+        let response = reqwest::get(url).await.unwrap().error_for_problem().await;
+
+        assert!(let Err(problem) = response);
+        check!(problem.status_code == StatusCode::NOT_FOUND);
+        check!(problem.body.is_empty());
+    }
+
+    /// A handler that forwards the problem from an outbound request with `?`.
+    async fn handler(url: String) -> crate::Result<String> {
+        let response = reqwest::get(url).await.unwrap().error_for_problem().await?;
+
+        Ok(response.text().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn question_mark() {
+        let url = serve(
+            StatusCode::NOT_FOUND,
+            "application/problem+json",
+            r#"{"title": "Account not found"}"#,
+        )
+        .await;
+
+        let result = handler(url).await;
+
+        assert!(let Err(problem) = result);
+        check!(problem.status_code == StatusCode::NOT_FOUND);
+        check!(problem.title() == Some("Account not found"));
+    }
+
+    #[tokio::test]
+    async fn invalid_problem_body() {
+        let url = serve(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "application/problem+json",
+            "Not JSON",
+        )
+        .await;
+
+        let response = reqwest::get(url).await.unwrap().error_for_problem().await;
+
+        assert!(let Err(problem) = response);
+        check!(problem.status_code == StatusCode::BAD_GATEWAY);
+        check!(problem.title() == Some("Bad Gateway"));
+        check!(problem.detail() == None);
+
+        let source = problem.source().unwrap();
+        check!(source.is::<FromResponseError>());
+    }
+}
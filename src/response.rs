@@ -0,0 +1,149 @@
+use http::{header::CONTENT_TYPE, HeaderValue, Response, StatusCode};
+use serde_json::Value;
+
+use super::{Format, Problem};
+
+impl Problem {
+    /// Render [`Problem::response_body`] as a JSON object.
+    pub(crate) fn response_json(&self) -> String {
+        self.filled_json(false)
+    }
+
+    /// Render [`Problem::filled_body`] as a JSON object.
+    pub(crate) fn filled_json(&self, send_empty: bool) -> String {
+        Value::Object(
+            self.filled_body(send_empty)
+                .into_owned()
+                .into_iter()
+                .collect(),
+        )
+        .to_string()
+    }
+
+    /// Get the status code and the body to send for the problem. This is shared by every HTTP
+    /// Server, so that they all send the same response.
+    ///
+    /// The body is `None` if the problem should be sent as just the status code. Otherwise it is
+    /// sent with the `Content-Type` of [`Format::ProblemJson`].
+    pub(crate) fn response_parts(&self) -> (StatusCode, Option<String>) {
+        let body = (!self.response_body().is_empty()).then(|| self.response_json());
+
+        (self.status_code, body)
+    }
+
+    /// Convert the problem into an HTTP response, for use with HTTP Servers that aren't otherwise
+    /// supported.
+    ///
+    /// The body is the JSON form of [`Problem::response_body`], with a `Content-Type` of
+    /// `application/problem+json`. Problems with an empty body are sent as just the status code.
+    /// The `Problem` is kept in the extensions of the response.
+    ///
+    /// # Examples
+    /// ```
+    /// # use http::StatusCode;
+    /// let response = problemdetails::new(StatusCode::FORBIDDEN)
+    ///     .with_title("You do not have enough credit.")
+    ///     .into_http_response();
+    ///
+    /// assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    /// assert_eq!(
+    ///     response.headers()["content-type"],
+    ///     "application/problem+json"
+    /// );
+    /// assert_eq!(
+    ///     response.body(),
+    ///     br#"{"title":"You do not have enough credit."}"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn into_http_response(self) -> Response<Vec<u8>> {
+        let (status_code, body) = self.response_parts();
+        let mut response = match body {
+            Some(body) => {
+                let mut response = Response::new(body.into_bytes());
+                response.headers_mut().insert(
+                    CONTENT_TYPE,
+                    HeaderValue::from_static(Format::ProblemJson.content_type()),
+                );
+                response
+            },
+            None => Response::new(Vec::new()),
+        };
+
+        *response.status_mut() = status_code;
+        response.extensions_mut().insert(self);
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use http::{header::CONTENT_TYPE, StatusCode};
+    use insta::assert_json_snapshot;
+    use serde_json::Value;
+
+    use crate::{AboutBlank, Problem, StatusMember};
+
+    #[test]
+    fn no_values() {
+        let response = crate::new(StatusCode::BAD_REQUEST).into_http_response();
+
+        check!(response.status() == StatusCode::BAD_REQUEST);
+        check!(response.headers().get(CONTENT_TYPE) == None);
+        check!(response.body().is_empty());
+    }
+
+    #[test]
+    fn rfc7807_forbidden_example() {
+        let response = crate::new(StatusCode::FORBIDDEN)
+            .with_type("https://example.com/probs/out-of-credit")
+            .with_title("You do not have enough credit.")
+            .with_detail("Your current balance is 30, but that costs 50.")
+            .with_instance("/account/12345/msgs/abc")
+            .with_value("balance", 30)
+            .with_value("accounts", vec!["/account/12345", "/account/67890"])
+            .into_http_response();
+
+        check!(response.status() == StatusCode::FORBIDDEN);
+        check!(response.headers().get(CONTENT_TYPE).unwrap() == "application/problem+json");
+        assert_json_snapshot!(serde_json::from_slice::<Value>(response.body()).unwrap(), @r###"
+        {
+          "accounts": [
+            "/account/12345",
+            "/account/67890"
+          ],
+          "balance": 30,
+          "detail": "Your current balance is 30, but that costs 50.",
+          "instance": "/account/12345/msgs/abc",
+          "title": "You do not have enough credit.",
+          "type": "https://example.com/probs/out-of-credit"
+        }
+        "###);
+    }
+
+    #[test]
+    fn applies_policies() {
+        let response = crate::new(StatusCode::NOT_FOUND)
+            .with_about_blank(AboutBlank::FillTitle)
+            .with_status_member(StatusMember::Insert)
+            .into_http_response();
+
+        assert_json_snapshot!(serde_json::from_slice::<Value>(response.body()).unwrap(), @r###"
+        {
+          "status": 404,
+          "title": "Not Found"
+        }
+        "###);
+    }
+
+    #[test]
+    fn problem_in_extensions() {
+        let response = crate::new(StatusCode::CONFLICT)
+            .with_title("Conflict")
+            .into_http_response();
+
+        let problem = response.extensions().get::<Problem>().unwrap();
+        check!(problem.title() == Some("Conflict"));
+    }
+}
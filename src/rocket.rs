@@ -0,0 +1,409 @@
+use std::{io::Cursor, sync::Mutex};
+
+use http::StatusCode;
+use rocket::{
+    catcher::{self, Catcher},
+    form::Errors,
+    http::Status,
+    request::Outcome,
+    response::{self, Responder},
+    Request, Response,
+};
+use serde_json::json;
+
+use super::{Format, Problem, ProblemType};
+
+/// The submitted form was not valid. The problem has an `invalid-params` extension member listing
+/// each field that was rejected and why, as in the example from RFC-9457.
+pub const INVALID_FORM: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/rocket/invalid-form",
+    StatusCode::UNPROCESSABLE_ENTITY,
+    "Your request parameters didn't validate.",
+);
+
+/// The last `Problem` that a request guard failed with, kept in the request-local cache so that the
+/// catcher can send it.
+///
+/// Rocket only caches the first value of each type, so this is a cell that later guard failures
+/// can replace.
+#[derive(Default)]
+struct GuardProblem(Mutex<Option<Problem>>);
+
+impl GuardProblem {
+    /// Get the problem that a guard failed with, if it has the status code that is being caught.
+    fn get(&self, status: Status) -> Option<Problem> {
+        let problem = self.0.lock().ok()?;
+
+        problem
+            .as_ref()
+            .filter(|problem| problem.status_code.as_u16() == status.code)
+            .cloned()
+    }
+
+    /// Replace the problem that a guard failed with.
+    fn set(&self, problem: Problem) {
+        if let Ok(mut current) = self.0.lock() {
+            *current = Some(problem);
+        }
+    }
+}
+
+/// Rocket uses its own type for status codes, so they have to be converted.
+fn status(status_code: StatusCode) -> Status {
+    Status::new(status_code.as_u16())
+}
+
+impl<'r> Responder<'r, 'static> for Problem {
+    fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'static> {
+        let (status_code, body) = self.response_parts();
+        let mut response = Response::build();
+        response.status(status(status_code));
+
+        if let Some(body) = body {
+            response
+                .raw_header("Content-Type", Format::ProblemJson.content_type())
+                .sized_body(body.len(), Cursor::new(body));
+        }
+
+        response.ok()
+    }
+}
+
+/// Convert the errors from a form into a problem, with an `invalid-params` member that has the
+/// `name` and `reason` of each error.
+///
+/// # Examples
+/// ```
+/// use problemdetails::Problem;
+/// use rocket::form::{Errors, Form, FromForm};
+///
+/// #[derive(FromForm)]
+/// struct User<'r> {
+///     name: &'r str,
+/// }
+///
+/// #[rocket::post("/users", data = "<user>")]
+/// fn create_user(user: Result<Form<User<'_>>, Errors<'_>>) -> Result<String, Problem> {
+///     Ok(user?.name.to_owned())
+/// }
+/// ```
+impl From<Errors<'_>> for Problem {
+    fn from(errors: Errors<'_>) -> Self {
+        let invalid_params: Vec<_> = errors
+            .iter()
+            .map(|error| {
+                json!({
+                    "name": error.name.as_ref().map(ToString::to_string),
+                    "reason": error.kind.to_string(),
+                })
+            })
+            .collect();
+
+        let mut problem = INVALID_FORM
+            .problem()
+            .with_value("invalid-params", invalid_params);
+        if let Ok(status) = StatusCode::from_u16(errors.status().code) {
+            problem.status_code = status;
+        }
+
+        problem
+    }
+}
+
+/// Fail a request guard with a `Problem`.
+///
+/// Rocket only gives catchers the status code of a failed request guard, so the problem is kept
+/// with the request to be sent by the catchers from [`catchers`]. It is only sent if the request
+/// fails with the same status code, so a guard failure that was handled, such as by an `Option`
+/// parameter, doesn't replace a later error.
+///
+/// # Examples
+/// ```
+/// # use http::StatusCode;
+/// use problemdetails::Problem;
+/// use rocket::request::{FromRequest, Outcome, Request};
+///
+/// struct ApiKey<'r>(&'r str);
+///
+/// #[rocket::async_trait]
+/// impl<'r> FromRequest<'r> for ApiKey<'r> {
+///     type Error = Problem;
+///
+///     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+///         match request.headers().get_one("x-api-key") {
+///             Some(key) => Outcome::Success(ApiKey(key)),
+///             None => problemdetails::rocket::guard_failure(
+///                 request,
+///                 problemdetails::new(StatusCode::UNAUTHORIZED).with_title("Missing API key"),
+///             ),
+///         }
+///     }
+/// }
+/// ```
+pub fn guard_failure<T>(request: &Request<'_>, problem: Problem) -> Outcome<T, Problem> {
+    request
+        .local_cache(GuardProblem::default)
+        .set(problem.clone());
+
+    Outcome::Error((status(problem.status_code), problem))
+}
+
+/// Catcher that sends a problem for every error status.
+fn default_catcher<'r>(status: Status, request: &'r Request<'_>) -> catcher::BoxFuture<'r> {
+    Box::pin(async move {
+        let problem = request
+            .local_cache(GuardProblem::default)
+            .get(status)
+            .unwrap_or_else(|| status_problem(status));
+
+        problem.respond_to(request)
+    })
+}
+
+/// Build the problem for an error status that has nothing more specific, with the reason phrase
+/// as the `title`.
+fn status_problem(status: Status) -> Problem {
+    crate::rejection::status_problem(
+        StatusCode::from_u16(status.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+    )
+}
+
+/// Catchers that replace Rocket's default error pages, such as for 404, 422 and 500, with
+/// problem responses.
+///
+/// If a request guard failed using [`guard_failure`] then that problem is sent instead.
+///
+/// # Examples
+/// ```
+/// let rocket = rocket::build().register("/", problemdetails::rocket::catchers());
+/// ```
+#[must_use]
+pub fn catchers() -> Vec<Catcher> {
+    vec![Catcher::new(None, default_catcher)]
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use http::StatusCode;
+    use insta::assert_json_snapshot;
+    use rocket::{
+        form::{Errors, Form},
+        get,
+        http::{ContentType, Status},
+        local::asynchronous::{Client, LocalResponse},
+        post,
+        request::{FromRequest, Outcome},
+        routes, FromForm, Request,
+    };
+    use serde_json::Value;
+
+    use crate::Problem;
+
+    #[derive(Debug, FromForm)]
+    struct User<'r> {
+        #[field(validate = len(1..))]
+        name: &'r str,
+        age:  u8,
+    }
+
+    struct ApiKey;
+
+    #[rocket::async_trait]
+    impl<'r> FromRequest<'r> for ApiKey {
+        type Error = Problem;
+
+        async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+            match request.headers().get_one("x-api-key") {
+                Some(_) => Outcome::Success(ApiKey),
+                None => super::guard_failure(
+                    request,
+                    crate::new(StatusCode::UNAUTHORIZED).with_title("Missing API key"),
+                ),
+            }
+        }
+    }
+
+    #[get("/empty")]
+    fn empty() -> Problem {
+        crate::new(StatusCode::BAD_REQUEST)
+    }
+
+    #[get("/forbidden")]
+    fn forbidden() -> Result<(), Problem> {
+        Err(crate::new(StatusCode::FORBIDDEN)
+            .with_type("https://example.com/probs/out-of-credit")
+            .with_title("You do not have enough credit.")
+            .with_detail("Your current balance is 30, but that costs 50.")
+            .with_value("balance", 30))
+    }
+
+    #[post("/users", data = "<user>")]
+    fn create_user(user: Result<Form<User<'_>>, Errors<'_>>) -> Result<String, Problem> {
+        let user = user?;
+
+        Ok(format!("{} is {}", user.name, user.age))
+    }
+
+    #[get("/secret")]
+    fn secret(_key: ApiKey) -> &'static str {
+        "secret"
+    }
+
+    #[get("/optional")]
+    fn optional(key: Option<ApiKey>) -> Option<&'static str> {
+        key.map(|_| "secret")
+    }
+
+    #[get("/panic")]
+    fn panic() -> &'static str {
+        panic!("Panic message");
+    }
+
+    async fn json(response: LocalResponse<'_>) -> Value {
+        serde_json::from_str(&response.into_string().await.unwrap()).unwrap()
+    }
+
+    async fn client() -> Client {
+        let rocket = rocket::build()
+            .mount(
+                "/",
+                routes![empty, forbidden, create_user, secret, optional, panic],
+            )
+            .register("/", super::catchers());
+
+        Client::tracked(rocket).await.unwrap()
+    }
+
+    #[rocket::async_test]
+    async fn no_values() {
+        let client = client().await;
+
+        let response = client.get("/empty").dispatch().await;
+
+        check!(response.status() == Status::BadRequest);
+        check!(response.content_type() == None);
+        check!(response.into_string().await == None);
+    }
+
+    #[rocket::async_test]
+    async fn error() {
+        let client = client().await;
+
+        let response = client.get("/forbidden").dispatch().await;
+
+        check!(response.status() == Status::Forbidden);
+        check!(response.content_type() == Some(ContentType::new("application", "problem+json")));
+        assert_json_snapshot!(json(response).await, @r###"
+        {
+          "balance": 30,
+          "detail": "Your current balance is 30, but that costs 50.",
+          "title": "You do not have enough credit.",
+          "type": "https://example.com/probs/out-of-credit"
+        }
+        "###);
+    }
+
+    #[rocket::async_test]
+    async fn form_errors() {
+        let client = client().await;
+
+        let response = client
+            .post("/users")
+            .header(ContentType::Form)
+            .body("name=&age=1000")
+            .dispatch()
+            .await;
+
+        check!(response.status() == Status::UnprocessableEntity);
+        check!(response.content_type() == Some(ContentType::new("application", "problem+json")));
+        assert_json_snapshot!(json(response).await, @r###"
+        {
+          "invalid-params": [
+            {
+              "name": "age",
+              "reason": "invalid integer: number too large to fit in target type"
+            },
+            {
+              "name": "name",
+              "reason": "cannot be empty"
+            }
+          ],
+          "title": "Your request parameters didn't validate.",
+          "type": "tag:sazzer.github.io,2026:problemdetails/rocket/invalid-form"
+        }
+        "###);
+    }
+
+    #[rocket::async_test]
+    async fn valid_form() {
+        let client = client().await;
+
+        let response = client
+            .post("/users")
+            .header(ContentType::Form)
+            .body("name=Bob&age=42")
+            .dispatch()
+            .await;
+
+        check!(response.status() == Status::Ok);
+        check!(response.into_string().await == Some("Bob is 42".to_owned()));
+    }
+
+    #[rocket::async_test]
+    async fn guard_failure() {
+        let client = client().await;
+
+        let response = client.get("/secret").dispatch().await;
+
+        check!(response.status() == Status::Unauthorized);
+        assert_json_snapshot!(json(response).await, @r###"
+        {
+          "title": "Missing API key"
+        }
+        "###);
+    }
+
+    #[rocket::async_test]
+    async fn handled_guard_failure() {
+        let client = client().await;
+
+        let response = client.get("/optional").dispatch().await;
+
+        check!(response.status() == Status::NotFound);
+        assert_json_snapshot!(json(response).await, @r###"
+        {
+          "title": "Not Found"
+        }
+        "###);
+    }
+
+    #[rocket::async_test]
+    async fn not_found() {
+        let client = client().await;
+
+        let response = client.get("/missing").dispatch().await;
+
+        check!(response.status() == Status::NotFound);
+        check!(response.content_type() == Some(ContentType::new("application", "problem+json")));
+        assert_json_snapshot!(json(response).await, @r###"
+        {
+          "title": "Not Found"
+        }
+        "###);
+    }
+
+    #[rocket::async_test]
+    async fn panic_in_handler() {
+        let client = client().await;
+
+        let response = client.get("/panic").dispatch().await;
+
+        check!(response.status() == Status::InternalServerError);
+        assert_json_snapshot!(json(response).await, @r###"
+        {
+          "title": "Internal Server Error"
+        }
+        "###);
+    }
+}
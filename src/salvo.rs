@@ -0,0 +1,113 @@
+use salvo::{Response, Scribe};
+
+use super::Problem;
+
+/// The response is built by [`Problem::into_http_response`], so the `Problem` is kept in the
+/// `extensions` of the response.
+impl Scribe for Problem {
+    fn render(self, res: &mut Response) {
+        let (parts, body) = self.into_http_response().into_parts();
+
+        res.status_code(parts.status);
+        res.headers_mut().extend(parts.headers);
+        res.extensions.extend(parts.extensions);
+        // This is set even when it is empty, because Salvo replaces error responses that have no
+        // body with its own error page.
+        res.body(body);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use http::{header::CONTENT_TYPE, StatusCode};
+    use insta::assert_json_snapshot;
+    use salvo::{
+        handler,
+        test::{ResponseExt, TestClient},
+        Router, Service,
+    };
+    use serde_json::Value;
+
+    use crate::Problem;
+
+    #[handler]
+    async fn no_value_handler() -> Problem {
+        crate::new(StatusCode::BAD_REQUEST)
+    }
+
+    #[handler]
+    async fn about_blank_handler() -> Problem {
+        crate::new(StatusCode::NOT_FOUND).with_about_blank(crate::AboutBlank::FillTitle)
+    }
+
+    #[handler]
+    async fn rfc7807_forbidden_example_handler() -> Result<String, Problem> {
+        Err(crate::new(StatusCode::FORBIDDEN)
+            .with_type("https://example.com/probs/out-of-credit")
+            .with_title("You do not have enough credit.")
+            .with_detail("Your current balance is 30, but that costs 50.")
+            .with_instance("/account/12345/msgs/abc")
+            .with_value("balance", 30)
+            .with_value("accounts", vec!["/account/12345", "/account/67890"]))
+    }
+
+    fn service() -> Service {
+        Service::new(
+            Router::new()
+                .push(Router::with_path("empty").get(no_value_handler))
+                .push(Router::with_path("about-blank").get(about_blank_handler))
+                .push(Router::with_path("forbidden").get(rfc7807_forbidden_example_handler)),
+        )
+    }
+
+    #[tokio::test]
+    async fn no_values() {
+        let mut response = TestClient::get("http://localhost/empty")
+            .send(&service())
+            .await;
+
+        check!(response.status_code == Some(StatusCode::BAD_REQUEST));
+        check!(response.headers().get(CONTENT_TYPE) == None);
+        check!(response.take_string().await.unwrap() == "");
+    }
+
+    #[tokio::test]
+    async fn about_blank() {
+        let mut response = TestClient::get("http://localhost/about-blank")
+            .send(&service())
+            .await;
+
+        check!(response.status_code == Some(StatusCode::NOT_FOUND));
+        check!(response.headers().get(CONTENT_TYPE).unwrap() == "application/problem+json");
+        assert_json_snapshot!(response.take_json::<Value>().await.unwrap(), @r###"
+        {
+          "title": "Not Found"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn rfc7807_forbidden_example() {
+        let mut response = TestClient::get("http://localhost/forbidden")
+            .send(&service())
+            .await;
+
+        check!(response.status_code == Some(StatusCode::FORBIDDEN));
+        check!(response.headers().get(CONTENT_TYPE).unwrap() == "application/problem+json");
+        check!(response.extensions.get::<Problem>().is_some());
+        assert_json_snapshot!(response.take_json::<Value>().await.unwrap(), @r###"
+        {
+          "accounts": [
+            "/account/12345",
+            "/account/67890"
+          ],
+          "balance": 30,
+          "detail": "Your current balance is 30, but that costs 50.",
+          "instance": "/account/12345/msgs/abc",
+          "title": "You do not have enough credit.",
+          "type": "https://example.com/probs/out-of-credit"
+        }
+        "###);
+    }
+}
@@ -0,0 +1,151 @@
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use serde_json::Value;
+
+use super::Problem;
+
+/// Whether the `status` member is inserted by default, as set by [`set_status_member`].
+static INSERT_STATUS: AtomicBool = AtomicBool::new(false);
+
+/// Policy for the `status` member in the body of problem responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatusMember {
+    /// The body is sent exactly as it was built. The `status` member is only present if it was
+    /// set manually.
+    #[default]
+    Manual,
+    /// The `status` member is written from the `status_code` of the problem, overwriting any
+    /// value that was set manually.
+    Insert,
+}
+
+/// Set the policy for the `status` member that is used by every problem that doesn't specify its
+/// own.
+///
+/// By default this is [`StatusMember::Manual`].
+///
+/// # Examples
+/// ```
+/// # use http::StatusCode;
+/// use problemdetails::StatusMember;
+///
+/// problemdetails::set_status_member(StatusMember::Insert);
+///
+/// let problem = problemdetails::new(StatusCode::NOT_FOUND).with_title("Not Found");
+/// assert_eq!(problem.response_body()["status"], 404);
+/// ```
+pub fn set_status_member(policy: StatusMember) {
+    INSERT_STATUS.store(policy == StatusMember::Insert, Ordering::Relaxed);
+}
+
+impl Problem {
+    /// Specify the policy for the `status` member to use for this problem, instead of the one set
+    /// by [`set_status_member`].
+    #[must_use]
+    pub fn with_status_member(mut self, policy: StatusMember) -> Self {
+        self.status_member = Some(policy);
+
+        self
+    }
+
+    /// Get the policy for the `status` member that applies to this problem.
+    #[must_use]
+    pub fn status_member(&self) -> StatusMember {
+        self.status_member.unwrap_or_else(|| {
+            if INSERT_STATUS.load(Ordering::Relaxed) {
+                StatusMember::Insert
+            } else {
+                StatusMember::Manual
+            }
+        })
+    }
+
+    /// Get the body to send to the client, with the `title` member applied according to the
+    /// [`AboutBlank`](crate::AboutBlank) policy, and the `status` member applied according to the
+    /// [`StatusMember`] policy for this problem.
+    ///
+    /// Problems that are still empty are left empty, so that they are still sent without a body.
+    #[must_use]
+    pub fn response_body(&self) -> Cow<'_, BTreeMap<String, Value>> {
+        self.filled_body(false)
+    }
+
+    /// Get the body to send to the client, as for [`Problem::response_body`].
+    ///
+    /// If `send_empty` is set then the body is sent even if it is empty, such as for
+    /// [`EmptyProblem::EmptyObject`](crate::EmptyProblem::EmptyObject), so the `status` member
+    /// is still applied.
+    pub(crate) fn filled_body(&self, send_empty: bool) -> Cow<'_, BTreeMap<String, Value>> {
+        let mut body = Cow::Borrowed(&self.body);
+
+        if let Some(title) = self.about_blank_title() {
+            body.to_mut().insert("title".to_owned(), title.into());
+        }
+        if (send_empty || !body.is_empty()) && self.status_member() == StatusMember::Insert {
+            body.to_mut()
+                .insert("status".to_owned(), self.status_code.as_u16().into());
+        }
+
+        body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use http::StatusCode;
+    use insta::assert_json_snapshot;
+
+    use super::StatusMember;
+
+    #[test]
+    fn manual() {
+        let problem = crate::new(StatusCode::NOT_FOUND)
+            .with_title("Not Found")
+            .with_status_member(StatusMember::Manual);
+
+        assert_json_snapshot!(problem.response_body(), @r###"
+        {
+          "title": "Not Found"
+        }
+        "###);
+    }
+
+    #[test]
+    fn insert() {
+        let problem = crate::new(StatusCode::NOT_FOUND)
+            .with_title("Not Found")
+            .with_status_member(StatusMember::Insert);
+
+        assert_json_snapshot!(problem.response_body(), @r###"
+        {
+          "status": 404,
+          "title": "Not Found"
+        }
+        "###);
+    }
+
+    #[test]
+    fn insert_overwrites_conflicting_status() {
+        let problem = crate::new(StatusCode::NOT_FOUND)
+            .with_value("status", 500)
+            .with_status_member(StatusMember::Insert);
+
+        assert_json_snapshot!(problem.response_body(), @r###"
+        {
+          "status": 404
+        }
+        "###);
+    }
+
+    #[test]
+    fn insert_into_empty_problem() {
+        let problem = crate::new(StatusCode::NOT_FOUND).with_status_member(StatusMember::Insert);
+
+        check!(problem.response_body().is_empty());
+    }
+}
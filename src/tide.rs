@@ -0,0 +1,141 @@
+use tide::{http::mime::Mime, Response, StatusCode};
+
+use super::{Format, Problem};
+
+/// The `Problem` is kept in the response, so that middleware can read it with `Response::ext`.
+///
+/// Tide turns any error returned from an endpoint into a response with only a status code, so
+/// problems should be returned as responses instead.
+///
+/// # Examples
+/// ```
+/// # use http::StatusCode;
+/// use tide::{Request, Response};
+///
+/// async fn forbidden(_req: Request<()>) -> tide::Result<Response> {
+///     Ok(problemdetails::new(StatusCode::FORBIDDEN)
+///         .with_title("You do not have enough credit.")
+///         .into())
+/// }
+/// ```
+impl From<Problem> for Response {
+    fn from(problem: Problem) -> Self {
+        let (status_code, body) = problem.response_parts();
+
+        // Tide has its own type for status codes, which doesn't include every valid status code.
+        let status =
+            StatusCode::try_from(status_code.as_u16()).unwrap_or(StatusCode::InternalServerError);
+        let mut response = Response::new(status);
+
+        if let Some(body) = body {
+            response.set_body(body);
+            response.set_content_type(
+                Format::ProblemJson
+                    .content_type()
+                    .parse::<Mime>()
+                    .expect("Could not parse the content type for problem detail"),
+            );
+        }
+
+        response.insert_ext(problem);
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use http::StatusCode;
+    use insta::assert_json_snapshot;
+    use serde_json::Value;
+    use tide::{
+        http::{Method, Request, Response, Url},
+        Server,
+    };
+
+    use crate::Problem;
+
+    fn app() -> Server<()> {
+        let mut app = tide::new();
+        app.at("/empty")
+            .get(|_| async { Ok(crate::new(StatusCode::BAD_REQUEST)) });
+        app.at("/about-blank").get(|_| async {
+            Ok(crate::new(StatusCode::NOT_FOUND).with_about_blank(crate::AboutBlank::FillTitle))
+        });
+        app.at("/forbidden").get(|_| async {
+            Ok(crate::new(StatusCode::FORBIDDEN)
+                .with_type("https://example.com/probs/out-of-credit")
+                .with_title("You do not have enough credit.")
+                .with_detail("Your current balance is 30, but that costs 50.")
+                .with_instance("/account/12345/msgs/abc")
+                .with_value("balance", 30)
+                .with_value("accounts", vec!["/account/12345", "/account/67890"]))
+        });
+
+        app
+    }
+
+    async fn get(path: &str) -> Response {
+        let url = Url::parse("http://localhost").unwrap().join(path).unwrap();
+
+        app().respond(Request::new(Method::Get, url)).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn no_values() {
+        let mut response = get("/empty").await;
+
+        check!(response.status() == 400);
+        check!(response.content_type() == None);
+        check!(response.body_string().await.unwrap() == "");
+    }
+
+    #[tokio::test]
+    async fn about_blank() {
+        let mut response = get("/about-blank").await;
+
+        check!(response.status() == 404);
+        check!(response.content_type().unwrap().essence() == "application/problem+json");
+        assert_json_snapshot!(response.body_json::<Value>().await.unwrap(), @r###"
+        {
+          "title": "Not Found"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn rfc7807_forbidden_example() {
+        let mut response = get("/forbidden").await;
+
+        check!(response.status() == 403);
+        check!(response.content_type().unwrap().essence() == "application/problem+json");
+        assert_json_snapshot!(response.body_json::<Value>().await.unwrap(), @r###"
+        {
+          "accounts": [
+            "/account/12345",
+            "/account/67890"
+          ],
+          "balance": 30,
+          "detail": "Your current balance is 30, but that costs 50.",
+          "instance": "/account/12345/msgs/abc",
+          "title": "You do not have enough credit.",
+          "type": "https://example.com/probs/out-of-credit"
+        }
+        "###);
+    }
+
+    #[test]
+    fn problem_in_extensions() {
+        let response = tide::Response::from(crate::new(StatusCode::CONFLICT));
+
+        check!(response.status() == 409);
+        check!(response.ext::<Problem>().is_some());
+    }
+
+    #[test]
+    fn unknown_status_code() {
+        let response = tide::Response::from(crate::new(StatusCode::from_u16(599).unwrap()));
+
+        check!(response.status() == 500);
+    }
+}
@@ -0,0 +1,77 @@
+//! Problem types for the errors that more than one HTTP Server can reject a request with.
+//!
+//! The same kind of error has the same "type" URI whichever HTTP Server rejected the request, so
+//! that clients can recognise it without knowing which one produced it. These are also available
+//! from the module for each HTTP Server, along with the types that only it uses.
+//!
+//! The status code of a problem is the one that the HTTP Server would have used, which might not
+//! be the status code of its type.
+//!
+//! # Examples
+//! ```
+//! use problemdetails::{types, Problem};
+//!
+//! fn is_bad_json(problem: &Problem) -> bool {
+//!     problem.is(&types::JSON_SYNTAX_ERROR) || problem.is(&types::JSON_DATA_ERROR)
+//! }
+//! ```
+
+use http::StatusCode;
+
+use super::ProblemType;
+
+/// The request body was valid JSON, but couldn't be deserialized into the target type.
+pub const JSON_DATA_ERROR: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/json-data-error",
+    StatusCode::UNPROCESSABLE_ENTITY,
+    "Invalid JSON data",
+);
+
+/// The request body was not valid JSON.
+pub const JSON_SYNTAX_ERROR: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/json-syntax-error",
+    StatusCode::BAD_REQUEST,
+    "Invalid JSON syntax",
+);
+
+/// The request didn't have a `Content-Type` of `application/json`.
+pub const MISSING_JSON_CONTENT_TYPE: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/missing-json-content-type",
+    StatusCode::UNSUPPORTED_MEDIA_TYPE,
+    "Expected a JSON request body",
+);
+
+/// The form in the request body couldn't be deserialized into the target type.
+pub const INVALID_FORM_BODY: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/invalid-form-body",
+    StatusCode::UNPROCESSABLE_ENTITY,
+    "Invalid form body",
+);
+
+/// The query string couldn't be deserialized into the target type.
+pub const INVALID_QUERY_STRING: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/invalid-query-string",
+    StatusCode::BAD_REQUEST,
+    "Invalid query string",
+);
+
+/// The path parameters couldn't be deserialized into the target type.
+pub const INVALID_PATH_PARAMS: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/invalid-path-params",
+    StatusCode::BAD_REQUEST,
+    "Invalid path parameters",
+);
+
+/// The request body was larger than the limit.
+pub const BODY_TOO_LARGE: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/body-too-large",
+    StatusCode::PAYLOAD_TOO_LARGE,
+    "Request body too large",
+);
+
+/// The request body couldn't be read.
+pub const FAILED_TO_READ_BODY: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/failed-to-read-body",
+    StatusCode::BAD_REQUEST,
+    "Failed to read request body",
+);
@@ -0,0 +1,219 @@
+use std::fmt;
+
+use http::StatusCode;
+use serde_json::Value;
+
+use super::Problem;
+
+/// Members that RFC-9457 requires to be strings.
+const STRING_MEMBERS: [&str; 4] = ["type", "title", "detail", "instance"];
+
+/// Errors from values that break the rules for the members defined by RFC-9457.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MemberError {
+    /// A member that must be a string had some other type of value.
+    NotAString {
+        /// The name of the member.
+        member: String,
+        /// The value that was given for the member.
+        value:  Value,
+    },
+    /// The `status` member was not a valid HTTP status code.
+    InvalidStatus(Value),
+    /// The `status` member did not match the `status_code` of the problem.
+    StatusMismatch {
+        /// The status code of the problem.
+        status_code: StatusCode,
+        /// The value that was given for the `status` member.
+        status:      u16,
+    },
+}
+
+impl fmt::Display for MemberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAString { member, value } => {
+                write!(f, "member \"{member}\" must be a string, but was {value}")
+            },
+            Self::InvalidStatus(value) => {
+                write!(
+                    f,
+                    "member \"status\" must be an HTTP status code, but was {value}"
+                )
+            },
+            Self::StatusMismatch {
+                status_code,
+                status,
+            } => write!(
+                f,
+                "member \"status\" was {status}, but the status code of the problem is {}",
+                status_code.as_u16()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MemberError {}
+
+impl Problem {
+    /// Specify an arbitrary value to include in the problem, checking that it is valid for the
+    /// members defined by RFC-9457.
+    ///
+    /// The "type", "title", "detail" and "instance" members must be strings, and the "status"
+    /// member must be the same HTTP status code as the `status_code` of the problem. Any other
+    /// member can have any value.
+    ///
+    /// # Parameters
+    /// - `key` - The key for the value.
+    /// - `value` - The value itself.
+    ///
+    /// # Errors
+    /// If the value is not valid for the member.
+    pub fn try_with_value<V>(self, key: &str, value: V) -> std::result::Result<Self, MemberError>
+    where
+        V: Into<Value>,
+    {
+        let value = value.into();
+        check_member(self.status_code, key, &value)?;
+
+        Ok(self.with_value(key, value))
+    }
+
+    /// Check that all of the members defined by RFC-9457 in the problem have valid values.
+    ///
+    /// # Errors
+    /// For the first member that has an invalid value.
+    pub fn validate(&self) -> std::result::Result<(), MemberError> {
+        self.body
+            .iter()
+            .try_for_each(|(key, value)| check_member(self.status_code, key, value))
+    }
+}
+
+/// Check that a value is valid for the given member.
+fn check_member(
+    status_code: StatusCode,
+    key: &str,
+    value: &Value,
+) -> std::result::Result<(), MemberError> {
+    if STRING_MEMBERS.contains(&key) && !value.is_string() {
+        return Err(MemberError::NotAString {
+            member: key.to_owned(),
+            value:  value.clone(),
+        });
+    }
+
+    if key == "status" {
+        let status = value
+            .as_u64()
+            .and_then(|status| u16::try_from(status).ok())
+            .filter(|status| StatusCode::from_u16(*status).is_ok())
+            .ok_or_else(|| MemberError::InvalidStatus(value.clone()))?;
+
+        if status != status_code.as_u16() {
+            return Err(MemberError::StatusMismatch {
+                status_code,
+                status,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{assert, check};
+    use http::StatusCode;
+    use serde_json::json;
+
+    use super::MemberError;
+
+    #[test]
+    fn valid_values() {
+        let problem = crate::new(StatusCode::FORBIDDEN)
+            .try_with_value("type", "https://example.com/probs/out-of-credit")
+            .and_then(|p| p.try_with_value("title", "You do not have enough credit."))
+            .and_then(|p| p.try_with_value("status", 403))
+            .and_then(|p| p.try_with_value("balance", 30));
+
+        assert!(let Ok(problem) = problem);
+        check!(problem.body.len() == 4);
+        check!(problem.validate() == Ok(()));
+    }
+
+    #[test]
+    fn non_string_title() {
+        let result = crate::new(StatusCode::BAD_REQUEST).try_with_value("title", 42);
+
+        assert!(let Err(err) = result);
+        check!(
+            err == MemberError::NotAString {
+                member: "title".to_owned(),
+                value:  json!(42),
+            }
+        );
+        check!(err.to_string() == "member \"title\" must be a string, but was 42");
+    }
+
+    #[test]
+    fn invalid_status() {
+        let result = crate::new(StatusCode::BAD_REQUEST).try_with_value("status", "400");
+
+        assert!(let Err(err) = result);
+        check!(err == MemberError::InvalidStatus(json!("400")));
+        check!(err.to_string() == "member \"status\" must be an HTTP status code, but was \"400\"");
+    }
+
+    #[test]
+    fn conflicting_status() {
+        let result = crate::new(StatusCode::BAD_REQUEST).try_with_value("status", 500);
+
+        assert!(let Err(err) = result);
+        check!(
+            err == MemberError::StatusMismatch {
+                status_code: StatusCode::BAD_REQUEST,
+                status:      500,
+            }
+        );
+        check!(
+            err.to_string()
+                == "member \"status\" was 500, but the status code of the problem is 400"
+        );
+    }
+
+    #[test]
+    fn validate_conflicting_status() {
+        let problem = crate::new(StatusCode::BAD_REQUEST).with_value("status", 500);
+
+        check!(
+            problem.validate()
+                == Err(MemberError::StatusMismatch {
+                    status_code: StatusCode::BAD_REQUEST,
+                    status:      500,
+                })
+        );
+    }
+
+    #[test]
+    fn any_extension_value() {
+        let result = crate::new(StatusCode::BAD_REQUEST).try_with_value("extra", json!({"a": 1}));
+
+        assert!(let Ok(_) = result);
+    }
+
+    #[test]
+    fn validate_existing_problem() {
+        let problem = crate::new(StatusCode::BAD_REQUEST)
+            .with_title("Bad Request")
+            .with_value("instance", json!(["/a", "/b"]));
+
+        check!(
+            problem.validate()
+                == Err(MemberError::NotAString {
+                    member: "instance".to_owned(),
+                    value:  json!(["/a", "/b"]),
+                })
+        );
+    }
+}
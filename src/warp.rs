@@ -0,0 +1,369 @@
+use std::{convert::Infallible, error::Error};
+
+use http::StatusCode;
+use warp::{
+    body::BodyDeserializeError,
+    reject::{
+        InvalidHeader, InvalidQuery, LengthRequired, MethodNotAllowed, MissingCookie,
+        MissingHeader, PayloadTooLarge, Reject, UnsupportedMediaType,
+    },
+    reply::Response,
+    Rejection, Reply,
+};
+
+pub use super::types::{
+    BODY_TOO_LARGE, INVALID_FORM_BODY, INVALID_QUERY_STRING, JSON_DATA_ERROR, JSON_SYNTAX_ERROR,
+};
+use super::{Problem, ProblemType};
+use crate::rejection::status_problem;
+
+/// A required request header was missing.
+pub const MISSING_HEADER: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/warp/missing-header",
+    StatusCode::BAD_REQUEST,
+    "Missing request header",
+);
+
+/// A request header had a value that couldn't be parsed.
+pub const INVALID_HEADER: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/warp/invalid-header",
+    StatusCode::BAD_REQUEST,
+    "Invalid request header",
+);
+
+/// A required cookie was missing.
+pub const MISSING_COOKIE: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/warp/missing-cookie",
+    StatusCode::BAD_REQUEST,
+    "Missing cookie",
+);
+
+/// The request didn't have a `Content-Length` header, but one is needed.
+pub const LENGTH_REQUIRED: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/warp/length-required",
+    StatusCode::LENGTH_REQUIRED,
+    "Length required",
+);
+
+/// The request had a `Content-Type` that isn't supported.
+pub const UNSUPPORTED_MEDIA_TYPE: ProblemType = ProblemType::new(
+    "tag:sazzer.github.io,2026:problemdetails/warp/unsupported-media-type",
+    StatusCode::UNSUPPORTED_MEDIA_TYPE,
+    "Unsupported media type",
+);
+
+/// The response is built by [`Problem::into_http_response`], so the `Problem` is kept in the
+/// extensions of the response.
+impl Reply for Problem {
+    fn into_response(self) -> Response {
+        self.into_http_response().map(Into::into)
+    }
+}
+
+/// Allows a request to be rejected with a `Problem`, using `warp::reject::custom`. The problem is
+/// sent by [`recover`].
+impl Reject for Problem {}
+
+/// Build the problem for a request body that couldn't be deserialized, which is either JSON or a
+/// form. Warp sends these as 400 Bad Request.
+fn body_problem(err: &BodyDeserializeError) -> Problem {
+    let problem_type = match err
+        .source()
+        .and_then(|source| source.downcast_ref::<serde_json::Error>())
+    {
+        Some(source) if source.is_data() => &JSON_DATA_ERROR,
+        Some(_) => &JSON_SYNTAX_ERROR,
+        None => &INVALID_FORM_BODY,
+    };
+
+    let mut problem = problem_type.with_detail(err.to_string());
+    problem.status_code = StatusCode::BAD_REQUEST;
+    problem
+}
+
+/// Get the problem to send for a rejection.
+///
+/// A `Problem` that the request was rejected with is used as it is. Rejections from warp's own
+/// filters become problems with the same status code that warp would have used, and any other
+/// custom rejection becomes a 500 Internal Server Error.
+///
+/// This can be used to write a recovery function that handles other custom rejections first.
+///
+/// # Examples
+/// ```
+/// use std::convert::Infallible;
+///
+/// use http::StatusCode;
+/// use problemdetails::Problem;
+/// use warp::Rejection;
+///
+/// #[derive(Debug)]
+/// struct OutOfCredit(u32);
+///
+/// impl warp::reject::Reject for OutOfCredit {}
+///
+/// async fn recover(rejection: Rejection) -> Result<Problem, Infallible> {
+///     if let Some(OutOfCredit(balance)) = rejection.find() {
+///         return Ok(problemdetails::new(StatusCode::FORBIDDEN)
+///             .with_title("You do not have enough credit.")
+///             .with_value("balance", *balance));
+///     }
+///
+///     Ok(problemdetails::warp::rejection_problem(&rejection))
+/// }
+/// ```
+#[must_use]
+pub fn rejection_problem(rejection: &Rejection) -> Problem {
+    if let Some(problem) = rejection.find::<Problem>() {
+        return problem.clone();
+    }
+
+    if let Some(err) = rejection.find::<BodyDeserializeError>() {
+        body_problem(err)
+    } else if let Some(err) = rejection.find::<InvalidQuery>() {
+        INVALID_QUERY_STRING.with_detail(err.to_string())
+    } else if let Some(err) = rejection.find::<MissingHeader>() {
+        MISSING_HEADER
+            .with_detail(err.to_string())
+            .with_value("header", err.name())
+    } else if let Some(err) = rejection.find::<InvalidHeader>() {
+        INVALID_HEADER
+            .with_detail(err.to_string())
+            .with_value("header", err.name())
+    } else if let Some(err) = rejection.find::<MissingCookie>() {
+        MISSING_COOKIE
+            .with_detail(err.to_string())
+            .with_value("cookie", err.name())
+    } else if let Some(err) = rejection.find::<LengthRequired>() {
+        LENGTH_REQUIRED.with_detail(err.to_string())
+    } else if let Some(err) = rejection.find::<PayloadTooLarge>() {
+        BODY_TOO_LARGE.with_detail(err.to_string())
+    } else if let Some(err) = rejection.find::<UnsupportedMediaType>() {
+        UNSUPPORTED_MEDIA_TYPE.with_detail(err.to_string())
+    } else if rejection.find::<MethodNotAllowed>().is_some() {
+        status_problem(StatusCode::METHOD_NOT_ALLOWED)
+    } else if rejection.is_not_found() {
+        status_problem(StatusCode::NOT_FOUND)
+    } else {
+        status_problem(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+}
+
+/// Recover from every rejection by sending a problem, for use with `warp::Filter::recover`.
+///
+/// See [`rejection_problem`] for the problem that is sent for each kind of rejection.
+///
+/// # Examples
+/// ```
+/// # use http::StatusCode;
+/// use warp::Filter;
+///
+/// let routes = warp::path!("users" / u32)
+///     .and_then(|id: u32| async move {
+///         Err::<String, _>(warp::reject::custom(
+///             problemdetails::new(StatusCode::NOT_FOUND).with_detail(format!("No user {id}")),
+///         ))
+///     })
+///     .recover(problemdetails::warp::recover);
+/// ```
+///
+/// # Errors
+/// This never fails, so that it can be used as the last recovery for a filter.
+pub async fn recover(rejection: Rejection) -> Result<Problem, Infallible> {
+    Ok(rejection_problem(&rejection))
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use http::{header::CONTENT_TYPE, StatusCode};
+    use insta::assert_json_snapshot;
+    use serde::Deserialize;
+    use serde_json::Value;
+    use warp::{reply::Reply, Filter, Rejection};
+
+    use crate::Problem;
+
+    #[derive(Debug, Deserialize)]
+    struct User {
+        name: String,
+    }
+
+    fn routes() -> impl Filter<Extract = (impl Reply,), Error = std::convert::Infallible> + Clone {
+        let empty = warp::path!("empty").map(|| crate::new(StatusCode::BAD_REQUEST));
+        let forbidden = warp::path!("forbidden").and_then(|| async {
+            Err::<String, Rejection>(warp::reject::custom(
+                crate::new(StatusCode::FORBIDDEN)
+                    .with_type("https://example.com/probs/out-of-credit")
+                    .with_title("You do not have enough credit.")
+                    .with_detail("Your current balance is 30, but that costs 50.")
+                    .with_value("balance", 30),
+            ))
+        });
+        let users = warp::path!("users")
+            .and(warp::post())
+            .and(warp::body::json())
+            .map(|user: User| user.name);
+        let search = warp::path!("search")
+            .and(warp::get())
+            .and(warp::query::<User>())
+            .map(|user: User| user.name);
+        let secret = warp::path!("secret")
+            .and(warp::header::<String>("x-api-key"))
+            .map(|_| "secret");
+
+        empty
+            .map(Reply::into_response)
+            .or(forbidden.map(Reply::into_response))
+            .unify()
+            .or(users.map(Reply::into_response))
+            .unify()
+            .or(search.map(Reply::into_response))
+            .unify()
+            .or(secret.map(Reply::into_response))
+            .unify()
+            .recover(super::recover)
+    }
+
+    fn json(body: &[u8]) -> Value {
+        serde_json::from_slice(body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn no_values() {
+        let response = warp::test::request().path("/empty").reply(&routes()).await;
+
+        check!(response.status() == StatusCode::BAD_REQUEST);
+        check!(response.headers().get(CONTENT_TYPE) == None);
+        check!(response.body().is_empty());
+    }
+
+    #[tokio::test]
+    async fn custom_rejection() {
+        let response = warp::test::request()
+            .path("/forbidden")
+            .reply(&routes())
+            .await;
+
+        check!(response.status() == StatusCode::FORBIDDEN);
+        check!(response.headers().get(CONTENT_TYPE).unwrap() == "application/problem+json");
+        assert_json_snapshot!(json(response.body()), @r###"
+        {
+          "balance": 30,
+          "detail": "Your current balance is 30, but that costs 50.",
+          "title": "You do not have enough credit.",
+          "type": "https://example.com/probs/out-of-credit"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn not_found() {
+        let response = warp::test::request()
+            .path("/missing")
+            .reply(&routes())
+            .await;
+
+        check!(response.status() == StatusCode::NOT_FOUND);
+        check!(response.headers().get(CONTENT_TYPE).unwrap() == "application/problem+json");
+        assert_json_snapshot!(json(response.body()), @r###"
+        {
+          "title": "Not Found"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn method_not_allowed() {
+        let response = warp::test::request()
+            .method("DELETE")
+            .path("/users")
+            .reply(&routes())
+            .await;
+
+        check!(response.status() == StatusCode::METHOD_NOT_ALLOWED);
+        assert_json_snapshot!(json(response.body()), @r###"
+        {
+          "title": "Method Not Allowed"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn body_deserialize_error() {
+        let response = warp::test::request()
+            .method("POST")
+            .path("/users")
+            .header(CONTENT_TYPE, "application/json")
+            .body("{")
+            .reply(&routes())
+            .await;
+
+        check!(response.status() == StatusCode::BAD_REQUEST);
+        assert_json_snapshot!(json(response.body()), @r###"
+        {
+          "detail": "Request body deserialize error: EOF while parsing an object at line 1 column 1",
+          "title": "Invalid JSON syntax",
+          "type": "tag:sazzer.github.io,2026:problemdetails/json-syntax-error"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn body_data_error() {
+        let response = warp::test::request()
+            .method("POST")
+            .path("/users")
+            .header(CONTENT_TYPE, "application/json")
+            .body("{}")
+            .reply(&routes())
+            .await;
+
+        check!(response.status() == StatusCode::BAD_REQUEST);
+        check!(json(response.body())["type"] == super::JSON_DATA_ERROR.uri());
+    }
+
+    #[tokio::test]
+    async fn invalid_query() {
+        let response = warp::test::request().path("/search").reply(&routes()).await;
+
+        check!(response.status() == StatusCode::BAD_REQUEST);
+        check!(json(response.body())["type"] == super::INVALID_QUERY_STRING.uri());
+    }
+
+    #[tokio::test]
+    async fn missing_header() {
+        let response = warp::test::request().path("/secret").reply(&routes()).await;
+
+        check!(response.status() == StatusCode::BAD_REQUEST);
+        assert_json_snapshot!(json(response.body()), @r###"
+        {
+          "detail": "Missing request header \"x-api-key\"",
+          "header": "x-api-key",
+          "title": "Missing request header",
+          "type": "tag:sazzer.github.io,2026:problemdetails/warp/missing-header"
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn success() {
+        let response = warp::test::request()
+            .path("/search?name=Bob")
+            .reply(&routes())
+            .await;
+
+        check!(response.status() == StatusCode::OK);
+        check!(response.body() == "Bob");
+    }
+
+    #[tokio::test]
+    async fn problem_in_extensions() {
+        let problem = crate::new(StatusCode::CONFLICT).with_title("Conflict");
+
+        let response = Reply::into_response(problem);
+
+        check!(response.status() == StatusCode::CONFLICT);
+        check!(response.extensions().get::<Problem>().is_some());
+    }
+}
@@ -0,0 +1,464 @@
+use std::{collections::BTreeMap, fmt, fmt::Write};
+
+use http::StatusCode;
+use quick_xml::{
+    escape::{escape, resolve_predefined_entity},
+    events::{BytesRef, BytesStart, Event},
+    name::{Namespace, ResolveResult},
+    NsReader,
+};
+use serde_json::{Map, Value};
+
+use super::Problem;
+
+/// The XML namespace for problem details, from RFC-9457 Appendix B.
+pub(crate) const NAMESPACE: &str = "urn:ietf:rfc:7807";
+
+/// The name of the element used for each entry in an array.
+const ARRAY_ITEM: &str = "i";
+
+/// Errors that can occur when converting a `Problem` to or from XML.
+#[derive(Debug)]
+pub enum XmlError {
+    /// The XML could not be parsed.
+    Parse(quick_xml::Error),
+    /// The root element was not a `problem` element in the problem details namespace.
+    InvalidRoot,
+    /// There was no `status` element.
+    MissingStatus,
+    /// The `status` element was not a valid HTTP status code.
+    InvalidStatus(String),
+    /// A member name can not be used as the name of an XML element.
+    InvalidName(String),
+}
+
+impl fmt::Display for XmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => write!(f, "invalid XML: {err}"),
+            Self::InvalidRoot => write!(f, "root element must be a problem in {NAMESPACE}"),
+            Self::MissingStatus => write!(f, "missing status element"),
+            Self::InvalidStatus(status) => write!(f, "invalid status: {status:?}"),
+            Self::InvalidName(name) => write!(f, "{name:?} is not a valid XML element name"),
+        }
+    }
+}
+
+impl std::error::Error for XmlError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl<E> From<E> for XmlError
+where
+    E: Into<quick_xml::Error>,
+{
+    fn from(err: E) -> Self {
+        Self::Parse(err.into())
+    }
+}
+
+impl Problem {
+    /// Serialize the problem as `application/problem+xml`, as described in RFC-9457 Appendix B.
+    ///
+    /// The status code is written as the `status` element. Extension members are written as
+    /// elements with the same name, with arrays written as a sequence of `i` elements and objects
+    /// written as nested elements.
+    ///
+    /// # Errors
+    /// If the name of a member can not be used as the name of an XML element.
+    pub fn to_xml(&self) -> std::result::Result<String, XmlError> {
+        self.body_xml(&self.body)
+    }
+
+    /// Serialize [`Problem::response_body`] as `application/problem+xml`, so that the policies for
+    /// the problem apply in the same way as for the other formats.
+    pub(crate) fn response_xml(&self) -> std::result::Result<String, XmlError> {
+        self.body_xml(&self.response_body())
+    }
+
+    /// Serialize the given body of the problem as `application/problem+xml`.
+    fn body_xml(&self, body: &BTreeMap<String, Value>) -> std::result::Result<String, XmlError> {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        writeln!(xml, "<problem xmlns=\"{NAMESPACE}\">").expect("Writing to a String can't fail");
+
+        let mut members: Vec<(&str, Value)> = body
+            .iter()
+            .filter(|(key, _)| key.as_str() != "status")
+            .map(|(key, value)| (key.as_str(), value.clone()))
+            .collect();
+        members.push(("status", self.status_code.as_u16().into()));
+        members.sort_by_key(|(key, _)| *key);
+
+        for (key, value) in &members {
+            write_element(&mut xml, 1, key, value)?;
+        }
+
+        xml.push_str("</problem>\n");
+        Ok(xml)
+    }
+
+    /// Parse a problem from `application/problem+xml`, as described in RFC-9457 Appendix B.
+    ///
+    /// The `status` element is used as the status code. Elements containing only `i` elements are
+    /// read as arrays, and other elements with children are read as objects. Since XML does not
+    /// carry the types of values, every other value is read as a string.
+    ///
+    /// # Errors
+    /// If the XML is not a valid problem, or if it does not have a valid `status` element.
+    pub fn from_xml(xml: &str) -> std::result::Result<Self, XmlError> {
+        let mut reader = NsReader::from_str(xml);
+
+        let body = loop {
+            match reader.read_resolved_event()? {
+                (namespace, Event::Start(element)) => {
+                    check_root(&namespace, &element)?;
+                    match read_value(&mut reader)? {
+                        Value::Object(members) => break members,
+                        _ => break Map::new(),
+                    }
+                },
+                (namespace, Event::Empty(element)) => {
+                    check_root(&namespace, &element)?;
+                    break Map::new();
+                },
+                (_, Event::Eof | Event::End(_) | Event::CData(_)) => {
+                    return Err(XmlError::InvalidRoot)
+                },
+                (_, Event::Text(text)) if !text.xml10_content()?.trim().is_empty() => {
+                    return Err(XmlError::InvalidRoot)
+                },
+                _ => {},
+            }
+        };
+
+        let mut body: BTreeMap<String, Value> = body.into_iter().collect();
+        let status = body.remove("status").ok_or(XmlError::MissingStatus)?;
+        let status = status.as_str().unwrap_or_default().trim();
+        let status_code = status
+            .parse::<u16>()
+            .ok()
+            .and_then(|status| StatusCode::from_u16(status).ok())
+            .ok_or_else(|| XmlError::InvalidStatus(status.to_owned()))?;
+
+        Ok(Self {
+            body,
+            ..crate::new(status_code)
+        })
+    }
+}
+
+/// Write a single value as an XML element, indented to the given depth.
+fn write_element(
+    xml: &mut String,
+    depth: usize,
+    name: &str,
+    value: &Value,
+) -> std::result::Result<(), XmlError> {
+    if !is_valid_name(name) {
+        return Err(XmlError::InvalidName(name.to_owned()));
+    }
+
+    let indent = "  ".repeat(depth);
+    match value {
+        Value::Null => writeln!(xml, "{indent}<{name}/>"),
+        Value::Bool(value) => writeln!(xml, "{indent}<{name}>{value}</{name}>"),
+        Value::Number(value) => writeln!(xml, "{indent}<{name}>{value}</{name}>"),
+        Value::String(value) => writeln!(xml, "{indent}<{name}>{}</{name}>", escape(value)),
+        Value::Array(values) => {
+            writeln!(xml, "{indent}<{name}>").expect("Writing to a String can't fail");
+            for value in values {
+                write_element(xml, depth + 1, ARRAY_ITEM, value)?;
+            }
+            writeln!(xml, "{indent}</{name}>")
+        },
+        Value::Object(members) => {
+            writeln!(xml, "{indent}<{name}>").expect("Writing to a String can't fail");
+            for (key, value) in members {
+                write_element(xml, depth + 1, key, value)?;
+            }
+            writeln!(xml, "{indent}</{name}>")
+        },
+    }
+    .expect("Writing to a String can't fail");
+
+    Ok(())
+}
+
+/// Check if a member name can be used as the name of an XML element.
+///
+/// This is stricter than the XML specification, since it doesn't allow namespace prefixes.
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// Check that the root element is a `problem` in the problem details namespace.
+fn check_root(
+    namespace: &ResolveResult,
+    element: &BytesStart,
+) -> std::result::Result<(), XmlError> {
+    let valid_namespace =
+        matches!(namespace, ResolveResult::Bound(Namespace(ns)) if *ns == NAMESPACE.as_bytes());
+
+    if valid_namespace && element.local_name().as_ref() == b"problem" {
+        Ok(())
+    } else {
+        Err(XmlError::InvalidRoot)
+    }
+}
+
+/// Read the contents of an element, up to and including its end tag, as a value.
+fn read_value(reader: &mut NsReader<&[u8]>) -> std::result::Result<Value, XmlError> {
+    let mut text = String::new();
+    let mut children = vec![];
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) => {
+                let name = element_name(&element)?;
+                children.push((name, read_value(reader)?));
+            },
+            Event::Empty(element) => {
+                children.push((element_name(&element)?, Value::String(String::new())));
+            },
+            Event::Text(value) => text.push_str(&value.xml10_content()?),
+            Event::CData(value) => text.push_str(&value.xml10_content()?),
+            Event::GeneralRef(reference) => text.push_str(&resolve_reference(&reference)?),
+            Event::End(_) => break,
+            Event::Eof => return Err(XmlError::InvalidRoot),
+            _ => {},
+        }
+    }
+
+    if children.is_empty() {
+        Ok(Value::String(text))
+    } else if children.iter().all(|(name, _)| name == ARRAY_ITEM) {
+        Ok(Value::Array(
+            children.into_iter().map(|(_, value)| value).collect(),
+        ))
+    } else {
+        Ok(Value::Object(children.into_iter().collect()))
+    }
+}
+
+/// Get the local name of an element.
+fn element_name(element: &BytesStart) -> std::result::Result<String, XmlError> {
+    Ok(reader_str(element.local_name().as_ref())?.to_owned())
+}
+
+/// Decode the name of an element as UTF-8.
+fn reader_str(value: &[u8]) -> std::result::Result<&str, XmlError> {
+    std::str::from_utf8(value)
+        .map_err(|err| XmlError::Parse(quick_xml::encoding::EncodingError::from(err).into()))
+}
+
+/// Resolve a character or entity reference into its text.
+fn resolve_reference(reference: &BytesRef) -> std::result::Result<String, XmlError> {
+    if let Some(c) = reference.resolve_char_ref()? {
+        return Ok(c.to_string());
+    }
+
+    let name = reference.decode()?;
+    resolve_predefined_entity(&name)
+        .map(ToOwned::to_owned)
+        .ok_or_else(|| {
+            XmlError::Parse(
+                quick_xml::escape::EscapeError::UnrecognizedEntity(0..name.len(), name.to_string())
+                    .into(),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{assert, check};
+    use http::StatusCode;
+    use insta::{assert_json_snapshot, assert_snapshot};
+    use serde_json::json;
+
+    use super::XmlError;
+    use crate::Problem;
+
+    #[test]
+    fn rfc9457_example() {
+        let problem = crate::new(StatusCode::FORBIDDEN)
+            .with_type("https://example.com/probs/out-of-credit")
+            .with_title("You do not have enough credit.")
+            .with_detail("Your current balance is 30, but that costs 50.")
+            .with_instance("https://example.net/account/12345/msgs/abc")
+            .with_value("balance", 30)
+            .with_value(
+                "accounts",
+                vec![
+                    "https://example.net/account/12345",
+                    "https://example.net/account/67890",
+                ],
+            );
+
+        assert_snapshot!(problem.to_xml().unwrap(), @r###"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <problem xmlns="urn:ietf:rfc:7807">
+          <accounts>
+            <i>https://example.net/account/12345</i>
+            <i>https://example.net/account/67890</i>
+          </accounts>
+          <balance>30</balance>
+          <detail>Your current balance is 30, but that costs 50.</detail>
+          <instance>https://example.net/account/12345/msgs/abc</instance>
+          <status>403</status>
+          <title>You do not have enough credit.</title>
+          <type>https://example.com/probs/out-of-credit</type>
+        </problem>
+        "###);
+    }
+
+    #[test]
+    fn nested_values() {
+        let problem = crate::new(StatusCode::BAD_REQUEST)
+            .with_title("Your request parameters didn't validate & can't be used.")
+            .with_value(
+                "invalid-params",
+                json!([
+                    {
+                        "name": "age",
+                        "reason": "must be a positive integer"
+                    },
+                    {
+                        "name": "color",
+                        "reason": "must be 'green', 'red' or 'blue'"
+                    }
+                ]),
+            )
+            .with_value("retry", json!({"allowed": false, "after": null}));
+
+        assert_snapshot!(problem.to_xml().unwrap(), @r###"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <problem xmlns="urn:ietf:rfc:7807">
+          <invalid-params>
+            <i>
+              <name>age</name>
+              <reason>must be a positive integer</reason>
+            </i>
+            <i>
+              <name>color</name>
+              <reason>must be &apos;green&apos;, &apos;red&apos; or &apos;blue&apos;</reason>
+            </i>
+          </invalid-params>
+          <retry>
+            <allowed>false</allowed>
+            <after/>
+          </retry>
+          <status>400</status>
+          <title>Your request parameters didn&apos;t validate &amp; can&apos;t be used.</title>
+        </problem>
+        "###);
+    }
+
+    #[test]
+    fn invalid_name() {
+        let problem = crate::new(StatusCode::BAD_REQUEST).with_value("not valid", 1);
+
+        assert!(let Err(XmlError::InvalidName(name)) = problem.to_xml());
+        check!(name == "not valid");
+    }
+
+    #[test]
+    fn parse_rfc9457_example() {
+        let problem = Problem::from_xml(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <problem xmlns="urn:ietf:rfc:7807">
+              <type>https://example.com/probs/out-of-credit</type>
+              <title>You do not have enough credit.</title>
+              <detail>Your current balance is 30, but that costs 50.</detail>
+              <instance>https://example.net/account/12345/msgs/abc</instance>
+              <balance>30</balance>
+              <accounts>
+                <i>https://example.net/account/12345</i>
+                <i>https://example.net/account/67890</i>
+              </accounts>
+              <status>403</status>
+            </problem>"#,
+        )
+        .unwrap();
+
+        check!(problem.status_code == StatusCode::FORBIDDEN);
+        assert_json_snapshot!(problem.body, @r###"
+        {
+          "accounts": [
+            "https://example.net/account/12345",
+            "https://example.net/account/67890"
+          ],
+          "balance": "30",
+          "detail": "Your current balance is 30, but that costs 50.",
+          "instance": "https://example.net/account/12345/msgs/abc",
+          "title": "You do not have enough credit.",
+          "type": "https://example.com/probs/out-of-credit"
+        }
+        "###);
+    }
+
+    #[test]
+    fn round_trip() {
+        let problem = crate::new(StatusCode::BAD_REQUEST)
+            .with_title("Can't parse <input> & \"quotes\"")
+            .with_value(
+                "invalid-params",
+                json!([{"name": "age", "reason": "must be positive"}]),
+            )
+            .with_value("nested", json!({"a": {"b": "c"}}));
+
+        let parsed = Problem::from_xml(&problem.to_xml().unwrap()).unwrap();
+
+        check!(parsed.status_code == problem.status_code);
+        check!(parsed.body == problem.body);
+    }
+
+    #[test]
+    fn prefixed_namespace() {
+        let problem = Problem::from_xml(
+            r#"<p:problem xmlns:p="urn:ietf:rfc:7807"><p:status>404</p:status></p:problem>"#,
+        )
+        .unwrap();
+
+        check!(problem.status_code == StatusCode::NOT_FOUND);
+        check!(problem.body.is_empty());
+    }
+
+    #[test]
+    fn wrong_namespace() {
+        let result = Problem::from_xml(r"<problem><status>404</status></problem>");
+
+        assert!(let Err(XmlError::InvalidRoot) = result);
+    }
+
+    #[test]
+    fn missing_status() {
+        let result = Problem::from_xml(r#"<problem xmlns="urn:ietf:rfc:7807"/>"#);
+
+        assert!(let Err(XmlError::MissingStatus) = result);
+    }
+
+    #[test]
+    fn invalid_status() {
+        let result = Problem::from_xml(
+            r#"<problem xmlns="urn:ietf:rfc:7807"><status>teapot</status></problem>"#,
+        );
+
+        assert!(let Err(XmlError::InvalidStatus(status)) = result);
+        check!(status == "teapot");
+    }
+
+    #[test]
+    fn malformed() {
+        let result = Problem::from_xml(r#"<problem xmlns="urn:ietf:rfc:7807"><status>"#);
+
+        assert!(let Err(_) = result);
+    }
+}